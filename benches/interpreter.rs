@@ -0,0 +1,55 @@
+use std::{cell::RefCell, hint::black_box, io, rc::Rc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const ARITHMETIC_LOOP: &str = "
+var sum = 0;
+for (var i = 0; i < 10000; i = i + 1) {
+  sum = sum + i * 2 - 1;
+}
+";
+
+const FIB: &str = "
+fun fib(n) {
+  if (n < 2) return n;
+  return fib(n - 1) + fib(n - 2);
+}
+fib(25);
+";
+
+const NESTED_LOOKUP: &str = "
+var global = 1;
+{
+  var a = 1;
+  {
+    var b = 2;
+    {
+      var c = 3;
+      {
+        var total = 0;
+        for (var i = 0; i < 2000; i = i + 1) {
+          total = total + global + a + b + c;
+        }
+      }
+    }
+  }
+}
+";
+
+fn run(source: &str) {
+    let output = Rc::new(RefCell::new(io::sink()));
+    rlox::run(black_box(source.to_string()), output).unwrap();
+}
+
+fn interpreter(c: &mut Criterion) {
+    c.bench_function("arithmetic_loop", |b| b.iter(|| run(ARITHMETIC_LOOP)));
+    c.bench_function("fib_25", |b| b.iter(|| run(FIB)));
+    c.bench_function("nested_scope_lookup", |b| b.iter(|| run(NESTED_LOOKUP)));
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = interpreter
+}
+criterion_main!(benches);
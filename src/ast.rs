@@ -1,16 +1,153 @@
-use crate::expr::Expr;
+use crate::{
+    expr::Expr,
+    stmt::{Stmt, StmtVar},
+};
 
 pub fn ast_print(expr: Expr) -> String {
     match expr {
-        Expr::Assign(expr) => unimplemented!(),
+        Expr::Assign(expr) => {
+            Expr::parenthesize(format!("= {}", expr.name.lexeme), vec![*expr.value])
+        }
         Expr::Binary(expr) => {
             Expr::parenthesize(expr.operator.lexeme, vec![*expr.left, *expr.right])
         }
+        Expr::Block(expr) => {
+            let value = expr
+                .value
+                .map_or("nil".to_string(), |value| ast_print(*value));
+            let block = stmts_parenthesize("block-expr".to_string(), &expr.statements);
+
+            format!("{} {})", &block[..block.len() - 1], value)
+        }
         Expr::Grouping(expr) => Expr::parenthesize("group".to_string(), vec![*expr.expr]),
-        Expr::Call(expr) => unimplemented!(),
+        Expr::Call(expr) => {
+            let mut exprs = vec![*expr.callee];
+            exprs.extend(expr.arguments);
+            let call = Expr::parenthesize("call".to_string(), exprs);
+
+            let keywords = expr
+                .keyword_arguments
+                .into_iter()
+                .map(|(name, value)| format!(" ({}: {})", name.lexeme, ast_print(value)))
+                .collect::<String>();
+
+            format!("{}{})", &call[..call.len() - 1], keywords)
+        }
         Expr::Literal(expr) => Expr::parenthesize(expr.literal.to_string(), Vec::new()),
-        Expr::Logical(expr) => unimplemented!(),
+        Expr::Logical(expr) => {
+            Expr::parenthesize(expr.operator.lexeme, vec![*expr.left, *expr.right])
+        }
         Expr::Unary(expr) => Expr::parenthesize(expr.operator.lexeme, vec![*expr.right]),
-        Expr::Var(expr) => unimplemented!(),
+        Expr::Var(expr) => Expr::parenthesize(expr.name.lexeme, Vec::new()),
     }
 }
+
+pub fn stmt_print(stmt: &Stmt) -> String {
+    match stmt {
+        Stmt::Block(stmt) => stmts_parenthesize("block".to_string(), &stmt.statements),
+        Stmt::Expression(stmt) => Expr::parenthesize(";".to_string(), vec![stmt.expr.clone()]),
+        Stmt::Print(stmt) => Expr::parenthesize("print".to_string(), vec![stmt.expr.clone()]),
+        Stmt::Write(stmt) => Expr::parenthesize("write".to_string(), vec![stmt.expr.clone()]),
+        Stmt::Return(stmt) => match &stmt.value {
+            Some(value) => Expr::parenthesize("return".to_string(), vec![value.clone()]),
+            None => "(return)".to_string(),
+        },
+        Stmt::Var(stmt) => var_print(stmt),
+        Stmt::VarMulti(stmt) => format!(
+            "(vars {})",
+            stmt.declarations
+                .iter()
+                .map(var_print)
+                .collect::<Vec<String>>()
+                .join(" ")
+        ),
+        Stmt::While(stmt) => match &stmt.else_branch {
+            Some(else_branch) => format!(
+                "(while {} {} {})",
+                ast_print(stmt.condition.clone()),
+                stmt_print(&stmt.body),
+                stmt_print(else_branch)
+            ),
+            None => format!(
+                "(while {} {})",
+                ast_print(stmt.condition.clone()),
+                stmt_print(&stmt.body)
+            ),
+        },
+        Stmt::For(stmt) => format!(
+            "(for {} {} {} {}{})",
+            stmt.initializer
+                .as_ref()
+                .map_or("_".to_string(), |initializer| stmt_print(initializer)),
+            stmt.condition.clone().map_or("_".to_string(), ast_print),
+            stmt.increment.clone().map_or("_".to_string(), ast_print),
+            stmt_print(&stmt.body),
+            stmt.else_branch
+                .as_ref()
+                .map_or(String::new(), |else_branch| format!(
+                    " {}",
+                    stmt_print(else_branch)
+                ))
+        ),
+        Stmt::Break(stmt) => match &stmt.label {
+            Some(label) => format!("(break {})", label.lexeme),
+            None => "(break)".to_string(),
+        },
+        Stmt::Continue(stmt) => match &stmt.label {
+            Some(label) => format!("(continue {})", label.lexeme),
+            None => "(continue)".to_string(),
+        },
+        Stmt::If(stmt) => match &stmt.else_branch {
+            Some(else_branch) => format!(
+                "(if {} {} {})",
+                ast_print(stmt.condition.clone()),
+                stmt_print(&stmt.then_branch),
+                stmt_print(else_branch)
+            ),
+            None => format!(
+                "(if {} {})",
+                ast_print(stmt.condition.clone()),
+                stmt_print(&stmt.then_branch)
+            ),
+        },
+        Stmt::Function(stmt) => {
+            let params = stmt
+                .params
+                .iter()
+                .map(|param| param.lexeme.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            stmts_parenthesize(format!("fun {} ({})", stmt.name.lexeme, params), &stmt.body)
+        }
+        Stmt::Import(stmt) => format!("(import {})", stmt.path.lexeme),
+        Stmt::Defer(stmt) => Expr::parenthesize("defer".to_string(), vec![stmt.expr.clone()]),
+        Stmt::Expect(stmt) => Expr::parenthesize("expect".to_string(), vec![stmt.expr.clone()]),
+        Stmt::Throw(stmt) => Expr::parenthesize("throw".to_string(), vec![stmt.value.clone()]),
+        Stmt::Try(stmt) => format!(
+            "{} {}",
+            stmts_parenthesize("try".to_string(), &stmt.body),
+            stmts_parenthesize(format!("catch {}", stmt.catch_var.lexeme), &stmt.catch_body)
+        ),
+    }
+}
+
+fn var_print(stmt: &StmtVar) -> String {
+    match &stmt.initializer {
+        Some(initializer) => Expr::parenthesize(
+            format!("var {} =", stmt.name.lexeme),
+            vec![initializer.clone()],
+        ),
+        None => format!("(var {})", stmt.name.lexeme),
+    }
+}
+
+fn stmts_parenthesize(name: String, stmts: &[Stmt]) -> String {
+    let mut stmts_string = String::new();
+    stmts.iter().for_each(|stmt| {
+        stmts_string.push(' ');
+        stmts_string.push_str(&stmt_print(stmt));
+    });
+
+    format!("({}{})", name, stmts_string)
+}
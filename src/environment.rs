@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{errors::InterpretError, interpreter::Evaluation, token::Token};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Environment {
     pub env: HashMap<String, Option<Evaluation>>,
     pub enclosing: Option<Box<Environment>>,
@@ -21,22 +21,27 @@ impl Environment {
     }
 
     pub fn get(&self, name: &Token) -> Result<Evaluation, InterpretError> {
-        if let Some(Some(evl)) = self.env.get(&name.lexeme) {
-            return Ok(evl.clone());
+        // A variable declared without an initializer reads as nil.
+        if let Some(evl) = self.env.get(&name.lexeme) {
+            return Ok(evl.clone().unwrap_or(Evaluation::nil(())));
         }
 
         if let Some(enc) = &self.enclosing {
-            return Ok(enc.get(name)?);
+            return enc.get(name);
         }
 
-        Err(InterpretError::RuntimeError {
-            err: format!(
-                "Undefined variable '{}' of token '{:#?}'.",
-                name.lexeme, name
-            ),
+        Err(InterpretError::UndefinedVariable {
+            lexeme: name.lexeme.to_string(),
         })
     }
 
+    pub fn get_ref(&self, name: &Token) -> Option<&Evaluation> {
+        match self.env.get(&name.lexeme) {
+            Some(value) => value.as_ref(),
+            None => self.enclosing.as_ref()?.get_ref(name),
+        }
+    }
+
     pub fn assign(&mut self, name: &Token, value: Evaluation) -> Result<(), InterpretError> {
         if self.env.contains_key(&name.lexeme) {
             self.env.insert(name.lexeme.to_string(), Some(value));
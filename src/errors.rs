@@ -1,4 +1,4 @@
-use std::io;
+use std::{fmt::Display, io};
 
 use thiserror::Error;
 
@@ -7,6 +7,22 @@ use crate::{
     token::{Token, TokenType},
 };
 
+pub const EXIT_COMPILE_ERROR: i32 = 65;
+pub const EXIT_RUNTIME_ERROR: i32 = 70;
+pub const EXIT_IO_ERROR: i32 = 74;
+
+#[derive(Error, Debug, Clone)]
+pub enum ScanError {
+    #[error("{line} Unexpected character '{character}'.")]
+    UnexpectedCharacter { character: char, line: usize },
+
+    #[error("{line} Unterminated string.")]
+    UnterminatedString { line: usize },
+
+    #[error("{line} Unterminated comment.")]
+    UnterminatedComment { line: usize },
+}
+
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("I/O fail, err: {err}")]
@@ -28,17 +44,51 @@ pub enum ParseError {
     ParseFail { token: Token, message: String },
 }
 
+fn describe(value: &Evaluation) -> String {
+    match value {
+        Evaluation::string(string) => format!("{string:?}"),
+        value => value.to_string(),
+    }
+}
+
+fn verb(operator_type: &TokenType) -> &'static str {
+    match operator_type {
+        TokenType::Minus => "subtract",
+        TokenType::Star => "multiply",
+        TokenType::Slash => "divide",
+        TokenType::Greater | TokenType::GreaterEqual | TokenType::Less | TokenType::LessEqual => {
+            "compare"
+        }
+        _ => "combine",
+    }
+}
+
 impl From<ParseError> for io::Error {
     fn from(error: ParseError) -> Self {
-        io::Error::new(io::ErrorKind::Other, format!("{:#?}", error))
+        io::Error::other(format!("{:#?}", error))
     }
 }
 
 #[derive(Error, Debug)]
 pub enum InterpretError {
+    #[error("[line {line}] {err}")]
+    AtLine {
+        line: usize,
+        err: Box<InterpretError>,
+    },
+
     #[error("{err}")]
     RuntimeError { err: String },
 
+    #[error("Exited with code {code}.")]
+    Exit { code: i32 },
+
+    #[error("{err}")]
+    LimitExceeded { err: String },
+
+    #[error("Thrown {value} was not caught.")]
+    Thrown { value: Evaluation, line: usize },
+
     #[error("Undefined variable '{lexeme}'.")]
     UndefinedVariable { lexeme: String },
 
@@ -48,19 +98,20 @@ pub enum InterpretError {
     #[error("Incorrect operand type, type: {operand_type:?}")]
     IncorrectOperandType { operand_type: TokenType },
 
-    #[error("Evaluation add overloader error, lhs: {lhs:#?}, rhs: {rhs:#?}")]
+    #[error("Cannot add '{}' and '{}'.", lhs.type_name(), rhs.type_name())]
     EvaluationAddOverloaderError { lhs: Evaluation, rhs: Evaluation },
 
-    #[error(
-        "Evaluate unary fail, right evaluation: {right_evaluation:#?}, operator type: {operator_type:#?}"
-    )]
+    #[error("Operand must be a number, got {}.", describe(right_evaluation))]
     EvaluateUnaryFail {
         right_evaluation: Evaluation,
         operator_type: TokenType,
     },
 
     #[error(
-        "Evaluate binary fail, left evaluation: {left_evaluation:#?}, operator type: {operator_type:#?}, right evaluation: {right_evaluation:#?}"
+        "Cannot {} '{}' and '{}'.",
+        verb(operator_type),
+        left_evaluation.type_name(),
+        right_evaluation.type_name()
     )]
     EvaluateBinaryFail {
         left_evaluation: Evaluation,
@@ -69,8 +120,64 @@ pub enum InterpretError {
     },
 }
 
+impl InterpretError {
+    pub fn uncaught(self) -> Self {
+        match self {
+            Self::Thrown { value, line } => Self::RuntimeError {
+                err: format!("Uncaught exception: {value}."),
+            }
+            .at_line(line),
+            err => err,
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            Self::AtLine { .. } | Self::Exit { .. } | Self::Thrown { .. } => self,
+            err => Self::AtLine {
+                line,
+                err: Box::new(err),
+            },
+        }
+    }
+}
+
 impl From<InterpretError> for io::Error {
     fn from(error: InterpretError) -> Self {
-        io::Error::new(io::ErrorKind::Other, format!("{:#?}", error))
+        io::Error::other(format!("{:#?}", error))
     }
 }
+
+#[derive(Error, Debug)]
+pub enum RloxError {
+    #[error("{}", display_errors(.0))]
+    Scan(Vec<ScanError>),
+
+    #[error("{}", display_errors(.0))]
+    Parse(Vec<ParseError>),
+
+    #[error("{0}")]
+    Runtime(InterpretError),
+
+    #[error("{0}")]
+    Io(io::Error),
+}
+
+impl RloxError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Scan(_) | Self::Parse(_) => EXIT_COMPILE_ERROR,
+            Self::Runtime(InterpretError::Exit { code }) => *code,
+            Self::Runtime(_) => EXIT_RUNTIME_ERROR,
+            Self::Io(_) => EXIT_IO_ERROR,
+        }
+    }
+}
+
+fn display_errors<T: Display>(errors: &[T]) -> String {
+    errors
+        .iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
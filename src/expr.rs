@@ -1,9 +1,16 @@
-use crate::token::{Literal, Token};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+use crate::{
+    ast::ast_print,
+    stmt::Stmt,
+    token::{Literal, Token},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Expr {
     Assign(ExprAssign),
     Binary(ExprBinary),
+    Block(ExprBlock),
     Call(ExprCall),
     Grouping(ExprGrouping),
     Literal(ExprLiteral),
@@ -13,34 +20,56 @@ pub enum Expr {
 }
 
 impl Expr {
+    pub fn span(&self) -> (usize, usize) {
+        match self {
+            Expr::Assign(expr) => expr.span,
+            Expr::Binary(expr) => expr.span,
+            Expr::Block(expr) => expr.span,
+            Expr::Call(expr) => expr.span,
+            Expr::Grouping(expr) => expr.span,
+            Expr::Literal(expr) => expr.span,
+            Expr::Logical(expr) => expr.span,
+            Expr::Unary(expr) => expr.span,
+            Expr::Var(expr) => expr.span,
+        }
+    }
+
+    pub fn with_span(mut self, span: (usize, usize)) -> Self {
+        *self.span_mut() = span;
+        self
+    }
+
+    fn span_mut(&mut self) -> &mut (usize, usize) {
+        match self {
+            Expr::Assign(expr) => &mut expr.span,
+            Expr::Binary(expr) => &mut expr.span,
+            Expr::Block(expr) => &mut expr.span,
+            Expr::Call(expr) => &mut expr.span,
+            Expr::Grouping(expr) => &mut expr.span,
+            Expr::Literal(expr) => &mut expr.span,
+            Expr::Logical(expr) => &mut expr.span,
+            Expr::Unary(expr) => &mut expr.span,
+            Expr::Var(expr) => &mut expr.span,
+        }
+    }
+
     pub fn parenthesize(name: String, exprs: Vec<Expr>) -> String {
         let mut exprs_string = String::new();
         exprs.into_iter().for_each(|expr| {
-            exprs_string.push_str(" ");
-
-            let child_expr = match expr {
-                Self::Assign(expr) => unimplemented!(),
-                Self::Binary(expr) => {
-                    Expr::parenthesize(expr.operator.lexeme, vec![*expr.left, *expr.right])
-                }
-                Self::Call(expr_literal) => unimplemented!(),
-                Self::Grouping(expr) => Expr::parenthesize("group".to_string(), vec![*expr.expr]),
-                Self::Literal(expr) => Expr::parenthesize(expr.literal.to_string(), Vec::new()),
-                Self::Logical(expr) => unimplemented!(),
-                Self::Unary(expr) => Expr::parenthesize(expr.operator.lexeme, vec![*expr.right]),
-                Self::Var(expr) => unimplemented!(),
-            };
-            exprs_string.push_str(&child_expr);
+            exprs_string.push(' ');
+            exprs_string.push_str(&ast_print(expr));
         });
 
         format!("({}{})", name, exprs_string)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprAssign {
     pub name: Token,
     pub value: Box<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprAssign {
@@ -48,15 +77,18 @@ impl ExprAssign {
         Self {
             name,
             value: Box::new(value),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprBinary {
     pub left: Box<Expr>,
     pub operator: Token,
     pub right: Box<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprBinary {
@@ -65,56 +97,95 @@ impl ExprBinary {
             left: Box::new(left_expr),
             operator: token,
             right: Box::new(right_expr),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExprBlock {
+    pub statements: Vec<Stmt>,
+    pub value: Option<Box<Expr>>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl ExprBlock {
+    pub fn new(statements: Vec<Stmt>, value: Option<Expr>) -> Self {
+        Self {
+            statements,
+            value: value.map(Box::new),
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprCall {
     pub callee: Box<Expr>,
     pub paren: Token,
     pub arguments: Vec<Expr>,
+    pub keyword_arguments: Vec<(Token, Expr)>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprCall {
-    pub fn new(callee: Expr, paren: Token, arguments: Vec<Expr>) -> Self {
+    pub fn new(
+        callee: Expr,
+        paren: Token,
+        arguments: Vec<Expr>,
+        keyword_arguments: Vec<(Token, Expr)>,
+    ) -> Self {
         Self {
             callee: Box::new(callee),
             paren,
             arguments,
+            keyword_arguments,
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprGrouping {
     pub expr: Box<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprGrouping {
     pub fn new(expr: Expr) -> Self {
         Self {
             expr: Box::new(expr),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprLiteral {
     pub literal: Literal,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprLiteral {
     pub fn new(literal: Literal) -> Self {
-        Self { literal }
+        Self {
+            literal,
+            span: (0, 0),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprLogical {
     pub left: Box<Expr>,
     pub operator: Token,
     pub right: Box<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprLogical {
@@ -123,14 +194,17 @@ impl ExprLogical {
             left: Box::new(left),
             operator,
             right: Box::new(right),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprUnary {
     pub operator: Token,
     pub right: Box<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprUnary {
@@ -138,17 +212,20 @@ impl ExprUnary {
         Self {
             operator: token,
             right: Box::new(right_expr),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExprVar {
     pub name: Token,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl ExprVar {
     pub fn new(name: Token) -> Self {
-        Self { name }
+        Self { name, span: (0, 0) }
     }
 }
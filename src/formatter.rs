@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+
+use crate::{
+    expr::Expr,
+    stmt::{Stmt, StmtVar},
+    token::{Literal, Token},
+};
+
+const INDENT: &str = "  ";
+
+pub fn format(statements: &[Stmt]) -> String {
+    format_with_comments(statements, "", Vec::new())
+}
+
+/// Formats `statements` parsed from `source`, keeping the `comments` scanned
+/// alongside them. A comment between statements stays on its own line, one
+/// after a statement on the same line stays trailing it, and one inside a
+/// statement moves to the line above that statement.
+pub fn format_with_comments(statements: &[Stmt], source: &str, comments: Vec<Token>) -> String {
+    let mut formatter = Formatter {
+        indent: 0,
+        source,
+        comments: comments.into(),
+    };
+
+    formatter
+        .statements(statements, usize::MAX)
+        .iter()
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+struct Formatter<'a> {
+    indent: usize,
+    source: &'a str,
+    comments: VecDeque<Token>,
+}
+
+impl Formatter<'_> {
+    /// Formats a statement list ending at byte `end`, with the comments that
+    /// fall inside it.
+    fn statements(&mut self, statements: &[Stmt], end: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for stmt in statements {
+            let (start, stmt_end) = stmt.span();
+            lines.extend(self.comments_before(start));
+            let line = self.stmt(stmt);
+            self.push_line(&mut lines, line, stmt_end, end);
+        }
+        lines.extend(self.comments_before(end));
+
+        lines
+    }
+
+    /// Pushes a formatted `line` ending at byte `line_end`, after the comments
+    /// inside it and with any comment that trails it before `end`.
+    fn push_line(
+        &mut self,
+        lines: &mut Vec<String>,
+        mut line: String,
+        line_end: usize,
+        end: usize,
+    ) {
+        lines.extend(self.comments_before(line_end));
+        if let Some(comment) = self.trailing_comment(line_end, end) {
+            line.push_str(&format!(" {comment}"));
+        }
+        lines.push(line);
+    }
+
+    fn comments_before(&mut self, offset: usize) -> Vec<String> {
+        let mut comments = Vec::new();
+        while self
+            .comments
+            .front()
+            .is_some_and(|comment| comment.span.0 < offset)
+        {
+            comments.extend(self.comments.pop_front().map(|comment| comment.lexeme));
+        }
+
+        comments
+    }
+
+    fn trailing_comment(&mut self, line_end: usize, end: usize) -> Option<String> {
+        let comment = self.comments.front()?;
+        let same_line = !self.source[line_end..comment.span.0].contains('\n');
+
+        match same_line && comment.span.0 < end {
+            true => self.comments.pop_front().map(|comment| comment.lexeme),
+            false => None,
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Block(stmt) => self.block(&stmt.statements, None, stmt.span.1),
+            Stmt::Expression(stmt) => format!("{};", self.expr(&stmt.expr)),
+            Stmt::Print(stmt) => format!("print {};", self.expr(&stmt.expr)),
+            Stmt::Write(stmt) => format!("write {};", self.expr(&stmt.expr)),
+            Stmt::Return(stmt) => match &stmt.value {
+                Some(value) => format!("return {};", self.expr(value)),
+                None => "return;".to_string(),
+            },
+            Stmt::Var(stmt) => format!("var {};", self.var(stmt)),
+            Stmt::VarMulti(stmt) => {
+                let declarations = stmt
+                    .declarations
+                    .iter()
+                    .map(|var| self.var(var))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!("var {declarations};")
+            }
+            Stmt::While(stmt) => {
+                let mut string = format!(
+                    "{}while ({}) {}",
+                    label(&stmt.label),
+                    self.expr(&stmt.condition),
+                    self.stmt(&stmt.body)
+                );
+                if let Some(else_branch) = &stmt.else_branch {
+                    string.push_str(&format!(" else {}", self.stmt(else_branch)));
+                }
+
+                string
+            }
+            Stmt::For(stmt) => {
+                let initializer = match &stmt.initializer {
+                    Some(initializer) => self.stmt(initializer),
+                    None => ";".to_string(),
+                };
+                let condition = match &stmt.condition {
+                    Some(condition) => format!(" {};", self.expr(condition)),
+                    None => ";".to_string(),
+                };
+                let increment = match &stmt.increment {
+                    Some(increment) => format!(" {}", self.expr(increment)),
+                    None => String::new(),
+                };
+
+                let mut string = format!(
+                    "{}for ({initializer}{condition}{increment}) {}",
+                    label(&stmt.label),
+                    self.stmt(&stmt.body)
+                );
+                if let Some(else_branch) = &stmt.else_branch {
+                    string.push_str(&format!(" else {}", self.stmt(else_branch)));
+                }
+
+                string
+            }
+            Stmt::Break(stmt) => match &stmt.label {
+                Some(label) => format!("break {};", label.lexeme),
+                None => "break;".to_string(),
+            },
+            Stmt::Continue(stmt) => match &stmt.label {
+                Some(label) => format!("continue {};", label.lexeme),
+                None => "continue;".to_string(),
+            },
+            Stmt::If(stmt) => {
+                let mut string = format!(
+                    "if ({}) {}",
+                    self.expr(&stmt.condition),
+                    self.stmt(&stmt.then_branch)
+                );
+                if let Some(else_branch) = &stmt.else_branch {
+                    string.push_str(&format!(" else {}", self.stmt(else_branch)));
+                }
+
+                string
+            }
+            Stmt::Function(stmt) => {
+                let params = stmt
+                    .params
+                    .iter()
+                    .map(|param| param.lexeme.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!(
+                    "fun {}({params}) {}",
+                    stmt.name.lexeme,
+                    self.block(&stmt.body, None, stmt.span.1)
+                )
+            }
+            Stmt::Import(stmt) => format!("import {};", stmt.path.lexeme),
+            Stmt::Expect(stmt) => format!("expect {};", self.expr(&stmt.expr)),
+            Stmt::Defer(stmt) => format!("defer {};", self.expr(&stmt.expr)),
+            Stmt::Throw(stmt) => format!("throw {};", self.expr(&stmt.value)),
+            Stmt::Try(stmt) => format!(
+                "try {} catch ({}) {}",
+                self.block(&stmt.body, None, stmt.catch_var.span.0),
+                stmt.catch_var.lexeme,
+                self.block(&stmt.catch_body, None, stmt.span.1)
+            ),
+        }
+    }
+
+    fn var(&mut self, stmt: &StmtVar) -> String {
+        match &stmt.initializer {
+            Some(initializer) => format!("{} = {}", stmt.name.lexeme, self.expr(initializer)),
+            None => stmt.name.lexeme.to_string(),
+        }
+    }
+
+    fn block(&mut self, statements: &[Stmt], value: Option<&Expr>, end: usize) -> String {
+        self.indent += 1;
+        let lines = match value {
+            Some(value) => {
+                let mut lines = self.statements(statements, value.span().0);
+                let line = self.expr(value);
+                self.push_line(&mut lines, line, value.span().1, end);
+                lines.extend(self.comments_before(end));
+                lines
+            }
+            None => self.statements(statements, end),
+        };
+        let inner = INDENT.repeat(self.indent);
+        self.indent -= 1;
+
+        if lines.is_empty() {
+            return "{}".to_string();
+        }
+
+        let body = lines
+            .iter()
+            .map(|line| format!("{inner}{line}\n"))
+            .collect::<String>();
+
+        format!("{{\n{body}{}}}", INDENT.repeat(self.indent))
+    }
+
+    fn expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Assign(expr) => format!("{} = {}", expr.name.lexeme, self.expr(&expr.value)),
+            Expr::Binary(expr) => format!(
+                "{} {} {}",
+                self.expr(&expr.left),
+                expr.operator.lexeme,
+                self.expr(&expr.right)
+            ),
+            Expr::Logical(expr) => format!(
+                "{} {} {}",
+                self.expr(&expr.left),
+                expr.operator.lexeme,
+                self.expr(&expr.right)
+            ),
+            Expr::Block(expr) => self.block(&expr.statements, expr.value.as_deref(), expr.span.1),
+            Expr::Call(expr) => {
+                let mut arguments = expr
+                    .arguments
+                    .iter()
+                    .map(|argument| self.expr(argument))
+                    .collect::<Vec<String>>();
+                arguments.extend(
+                    expr.keyword_arguments
+                        .iter()
+                        .map(|(name, value)| format!("{}: {}", name.lexeme, self.expr(value))),
+                );
+
+                format!("{}({})", self.expr(&expr.callee), arguments.join(", "))
+            }
+            Expr::Grouping(expr) => format!("({})", self.expr(&expr.expr)),
+            Expr::Literal(expr) => literal(&expr.literal),
+            Expr::Unary(expr) => format!("{}{}", expr.operator.lexeme, self.expr(&expr.right)),
+            Expr::Var(expr) => expr.name.lexeme.to_string(),
+        }
+    }
+}
+
+fn label(label: &Option<Token>) -> String {
+    match label {
+        Some(label) => format!("{}: ", label.lexeme),
+        None => String::new(),
+    }
+}
+
+fn literal(literal: &Literal) -> String {
+    match literal {
+        Literal::string(string) => format!("\"{string}\""),
+        Literal::f64(f64) => {
+            let number = f64.to_string();
+            match number.contains('.') {
+                true => number,
+                false => format!("{number}.0"),
+            }
+        }
+        literal => literal.to_string(),
+    }
+}
@@ -1,16 +1,27 @@
 use std::{
+    cell::RefCell,
+    collections::HashSet,
     fmt::{Debug, Display},
-    io,
-    ops::Add,
-    process::exit,
+    fs,
+    io::{self, BufRead, Write},
+    mem,
+    ops::{Add, Deref, DerefMut},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, Instant},
 };
 
 use crate::{
+    ast::ast_print,
     environment::Environment,
     errors::InterpretError,
-    expr::Expr,
-    stmt::{Stmt, StmtFunction},
-    token::{Literal, TokenType},
+    expr::{Expr, ExprAssign, ExprBinary, ExprBlock, ExprCall, ExprLogical, ExprUnary},
+    profile::Profile,
+    stmt::{
+        Stmt, StmtExpect, StmtFor, StmtFunction, StmtIf, StmtReturn, StmtThrow, StmtTry, StmtVar,
+        StmtWhile,
+    },
+    token::{Literal, Token, TokenType},
 };
 
 #[derive(Debug, Clone)]
@@ -23,6 +34,105 @@ pub enum Evaluation {
     callable(Box<dyn Callable>),
 }
 
+#[derive(Debug)]
+pub enum Flow {
+    Normal,
+    Return(Evaluation),
+    Break(Option<String>),
+    Continue(Option<String>),
+}
+
+fn check_arity(function: &dyn Callable, count: usize) -> Result<(), InterpretError> {
+    let (min_arity, arity) = (function.min_arity(), function.arity());
+
+    if !(min_arity as usize..=arity as usize).contains(&count) {
+        let expected = match min_arity == arity {
+            true => arity.to_string(),
+            false => format!("{min_arity} to {arity}"),
+        };
+
+        return Err(InterpretError::RuntimeError {
+            err: format!("Expected '{expected}' arguments but got '{count}'"),
+        });
+    }
+
+    Ok(())
+}
+
+// An unlabeled break/continue targets the innermost loop; a labeled one
+// unwinds until it reaches the loop carrying that label.
+fn targets(label: &Option<String>, loop_label: &Option<Token>) -> bool {
+    match (label, loop_label) {
+        (None, _) => true,
+        (Some(label), Some(loop_label)) => *label == loop_label.lexeme,
+        (Some(_), None) => false,
+    }
+}
+
+impl Evaluation {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Evaluation::string(_) => "string",
+            Evaluation::f64(_) => "number",
+            Evaluation::bool(_) => "bool",
+            Evaluation::nil(_) => "nil",
+            Evaluation::callable(_) => "function",
+        }
+    }
+
+    pub fn to_json(&self) -> Result<serde_json::Value, InterpretError> {
+        let value = match self {
+            Evaluation::string(string) => serde_json::Value::from(string.as_str()),
+            Evaluation::f64(f64) if f64.fract() == 0.0 && f64.abs() < i64::MAX as f64 => {
+                serde_json::Value::from(*f64 as i64)
+            }
+            Evaluation::f64(f64) => match serde_json::Number::from_f64(*f64) {
+                Some(number) => serde_json::Value::Number(number),
+                None => {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!("Can't convert {self} to JSON."),
+                    })
+                }
+            },
+            Evaluation::bool(bool) => serde_json::Value::from(*bool),
+            Evaluation::nil(_) => serde_json::Value::Null,
+            Evaluation::callable(_) => {
+                return Err(InterpretError::RuntimeError {
+                    err: format!("Can't convert {self} to JSON."),
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    pub fn from_json(json: serde_json::Value) -> Result<Evaluation, InterpretError> {
+        let value = match json {
+            serde_json::Value::Null => Evaluation::nil(()),
+            serde_json::Value::Bool(bool) => Evaluation::bool(bool),
+            serde_json::Value::Number(number) => {
+                Evaluation::f64(number.as_f64().unwrap_or(f64::NAN))
+            }
+            serde_json::Value::String(string) => Evaluation::string(string),
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                return Err(InterpretError::RuntimeError {
+                    err: "fromJson can't convert arrays or objects yet.".to_string(),
+                })
+            }
+        };
+
+        Ok(value)
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Evaluation::nil(()) => false,
+            Evaluation::bool(bool) => *bool,
+            _ => true,
+        }
+    }
+}
+
 impl From<Evaluation> for Result<Box<dyn Callable>, InterpretError> {
     fn from(value: Evaluation) -> Self {
         if let Evaluation::callable(rlox_callable) = value {
@@ -38,13 +148,36 @@ impl From<Evaluation> for Result<Box<dyn Callable>, InterpretError> {
 
 pub trait Callable: CallableClone {
     fn arity(&self) -> u8;
+    fn display(&self) -> String;
+
+    // Fewest arguments a call may pass; differs from `arity` once parameters
+    // can be optional.
+    fn min_arity(&self) -> u8 {
+        self.arity()
+    }
     fn call(
         &mut self,
         interpreter: &mut Interpreter,
         callee: String,
         arguments: Vec<Evaluation>,
     ) -> Result<Option<Evaluation>, InterpretError>;
-    fn display(&self) -> String;
+
+    fn bind_arguments(
+        &self,
+        arguments: Vec<Evaluation>,
+        keyword_arguments: Vec<(Token, Evaluation)>,
+    ) -> Result<Vec<Evaluation>, InterpretError> {
+        match keyword_arguments.first() {
+            Some((name, _)) => Err(InterpretError::RuntimeError {
+                err: format!(
+                    "{} doesn't accept keyword argument '{}'.",
+                    self.display(),
+                    name.lexeme
+                ),
+            }),
+            None => Ok(arguments),
+        }
+    }
 }
 
 pub trait CallableClone {
@@ -72,20 +205,20 @@ impl Debug for Box<dyn Callable> {
     }
 }
 
+pub type NativeFn =
+    fn(&mut Interpreter, Vec<Evaluation>) -> Result<Option<Evaluation>, InterpretError>;
+
 #[derive(Clone)]
 pub struct NativeFunction {
     pub arity: u8,
     pub fn_name: String,
-    pub fun: Option<fn(&mut Interpreter, Vec<Evaluation>) -> Option<Evaluation>>,
+    pub fun: Option<NativeFn>,
 }
 
 impl NativeFunction {
-    pub fn new(
-        fn_name: String,
-        fun: fn(&mut Interpreter, Vec<Evaluation>) -> Option<Evaluation>,
-    ) -> Self {
+    pub fn new(fn_name: String, arity: u8, fun: NativeFn) -> Self {
         Self {
-            arity: 0,
+            arity,
             fn_name,
             fun: Some(fun),
         }
@@ -103,32 +236,31 @@ impl Callable for NativeFunction {
         _callee: String,
         arguments: Vec<Evaluation>,
     ) -> Result<Option<Evaluation>, InterpretError> {
-        Ok((self.fun.as_mut().unwrap())(interpreter, arguments))
+        (self.fun.as_mut().unwrap())(interpreter, arguments)
     }
 
     fn display(&self) -> String {
-        self.fn_name.to_string()
+        format!("<native fn {}>", self.fn_name)
     }
 }
 
 #[derive(Clone)]
 pub struct RloxFunction {
-    pub arity: u8,
+    pub min_arity: u8,
+    pub max_arity: u8,
     pub fn_name: String,
     pub declaration: StmtFunction,
     pub closure: Box<Environment>,
 }
 
 impl RloxFunction {
-    pub fn new(
-        arity: u8,
-        fn_name: String,
-        declaration: StmtFunction,
-        closure: Box<Environment>,
-    ) -> Self {
+    pub fn new(declaration: StmtFunction, closure: Box<Environment>) -> Self {
+        let arity = declaration.params.len() as u8;
+
         Self {
-            arity,
-            fn_name,
+            min_arity: arity,
+            max_arity: arity,
+            fn_name: declaration.name.lexeme.to_string(),
             declaration,
             closure,
         }
@@ -137,7 +269,11 @@ impl RloxFunction {
 
 impl Callable for RloxFunction {
     fn arity(&self) -> u8 {
-        self.arity
+        self.max_arity
+    }
+
+    fn min_arity(&self) -> u8 {
+        self.min_arity
     }
 
     fn call(
@@ -146,38 +282,91 @@ impl Callable for RloxFunction {
         callee: String,
         arguments: Vec<Evaluation>,
     ) -> Result<Option<Evaluation>, InterpretError> {
+        if interpreter.call_depth >= interpreter.max_call_depth {
+            return Err(InterpretError::LimitExceeded {
+                err: "Stack overflow.".to_string(),
+            });
+        }
+
         let mut environment = self.closure.clone();
-        environment.enclosing = Some(Box::new(interpreter.environment.clone()));
 
         let mut declaration = self.declaration.clone();
 
-        println!("----------------");
-        for i in 0..declaration.params.len() {
-            println!(
-                "{:#?}{:#?}",
-                declaration.params[i].lexeme.to_string(),
-                Some(arguments[i].clone()),
-            );
-
-            environment.define(
-                declaration.params[i].lexeme.to_string(),
-                Some(arguments[i].clone()),
-            )
+        for (param, argument) in declaration.params.iter().zip(arguments.iter()) {
+            environment.define(param.lexeme.to_string(), Some(argument.clone()))
         }
 
-        let a = interpreter.stmt_execute_block(&mut declaration.body, &mut environment)?;
+        // Every few calls, move onto a fresh stack segment. The depth limit then
+        // holds whatever stack the host thread was given.
+        let new_segment = interpreter
+            .call_depth
+            .is_multiple_of(CALLS_PER_STACK_SEGMENT);
+        interpreter.call_depth += 1;
+        let mut run = || interpreter.stmt_execute_block(&mut declaration.body, &mut environment);
+        let a = match new_segment {
+            true => stacker::grow(CALL_STACK_SEGMENT, run),
+            false => run(),
+        };
+        interpreter.call_depth -= 1;
+        let a = a?;
 
         self.closure = environment.clone();
 
+        // Write the updated closure back to the binding the callee was looked up
+        // through, rather than defining the name again in the caller's scope.
+        let name = Token::new(TokenType::Identifier, callee, None, 0, (0, 0));
         interpreter
             .environment
-            .define(callee, Some(Evaluation::callable(Box::new(self.clone()))));
+            .assign(&name, Evaluation::callable(Box::new(self.clone())))?;
 
-        Ok(a)
+        match a {
+            Flow::Return(value) => Ok(Some(value)),
+            _ => Ok(None),
+        }
     }
 
     fn display(&self) -> String {
-        format!("{:#?}", self.fn_name)
+        format!("<fn {}>", self.fn_name)
+    }
+
+    fn bind_arguments(
+        &self,
+        arguments: Vec<Evaluation>,
+        keyword_arguments: Vec<(Token, Evaluation)>,
+    ) -> Result<Vec<Evaluation>, InterpretError> {
+        let params = &self.declaration.params;
+        if keyword_arguments.is_empty() || arguments.len() > params.len() {
+            return Ok(arguments);
+        }
+
+        let mut bound = arguments.into_iter().map(Some).collect::<Vec<_>>();
+        bound.resize(params.len(), None);
+
+        for (name, value) in keyword_arguments {
+            match params.iter().position(|param| param.lexeme == name.lexeme) {
+                Some(index) if bound[index].is_none() => bound[index] = Some(value),
+                Some(_) => {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!("Argument '{}' given more than once.", name.lexeme),
+                    })
+                }
+                None => {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!("Unknown parameter '{}'.", name.lexeme),
+                    })
+                }
+            }
+        }
+
+        bound
+            .into_iter()
+            .zip(params)
+            .map(|(value, param)| {
+                value.ok_or_else(|| InterpretError::RuntimeError {
+                    err: format!("Missing argument for parameter '{}'.", param.lexeme),
+                })
+            })
+            .collect()
     }
 }
 
@@ -188,7 +377,7 @@ impl Display for Evaluation {
             Evaluation::f64(f64) => write!(f, "{f64:.2}"),
             Evaluation::bool(bool) => write!(f, "{bool}"),
             Evaluation::nil(()) => write!(f, "nil"),
-            Evaluation::callable(fun) => write!(f, "fn <{:#?}>", fun.display()),
+            Evaluation::callable(fun) => write!(f, "{}", fun.display()),
         }
     }
 }
@@ -222,10 +411,51 @@ impl PartialEq for Evaluation {
     }
 }
 
+// Hashable subset of Evaluation for use as map keys. Numbers compare by bit pattern with
+// -0 folded into 0, so 1 and 1.0 (both Evaluation::f64) are the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum EvaluationKey {
+    string(String),
+    f64(u64),
+    bool(bool),
+    nil(()),
+}
+
+impl TryFrom<&Evaluation> for EvaluationKey {
+    type Error = InterpretError;
+
+    fn try_from(value: &Evaluation) -> Result<Self, Self::Error> {
+        match value {
+            Evaluation::string(string) => Ok(Self::string(string.clone())),
+            Evaluation::f64(f64) => Ok(Self::f64((f64 + 0.0).to_bits())),
+            Evaluation::bool(bool) => Ok(Self::bool(*bool)),
+            Evaluation::nil(()) => Ok(Self::nil(())),
+            Evaluation::callable(callable) => Err(InterpretError::RuntimeError {
+                err: format!("Can't use {} as a map key.", callable.display()),
+            }),
+        }
+    }
+}
+
+impl From<EvaluationKey> for Evaluation {
+    fn from(key: EvaluationKey) -> Self {
+        match key {
+            EvaluationKey::string(string) => Self::string(string),
+            EvaluationKey::f64(bits) => Self::f64(f64::from_bits(bits)),
+            EvaluationKey::bool(bool) => Self::bool(bool),
+            EvaluationKey::nil(nil) => Self::nil(nil),
+        }
+    }
+}
+
 impl From<Literal> for Evaluation {
     fn from(literal: Literal) -> Self {
         match literal {
             Literal::string(string) => Self::string(string),
+            // Evaluation only has one number type, so `3 / 2` is 1.5 whichever way the
+            // operands were written.
+            Literal::int(int) => Self::f64(int as f64),
             Literal::f64(f64) => Self::f64(f64),
             Literal::bool(bool) => Self::bool(bool),
             Literal::nil(nil) => Self::nil(nil),
@@ -233,245 +463,1168 @@ impl From<Literal> for Evaluation {
     }
 }
 
+const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+// A Lox call nests a dozen or so interpreter frames, around 10 KiB in debug
+// builds, so a segment leaves plenty of room for its calls.
+const CALLS_PER_STACK_SEGMENT: usize = 64;
+const CALL_STACK_SEGMENT: usize = 4 * 1024 * 1024;
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
+// Rust's formatter panics on precisions past u16::MAX; anything near that is a mistake.
+const MAX_FMT_NUM_DIGITS: usize = 100;
+
 #[derive(Clone)]
 pub struct Interpreter {
     pub globals: Environment,
     pub environment: Environment,
+    pub call_depth: usize,
+    pub max_call_depth: usize,
+    pub step_budget: Option<u64>,
+    pub steps: u64,
+    pub output: Rc<RefCell<dyn Write>>,
+    pub input: Rc<RefCell<dyn BufRead>>,
+    pub import_stack: Vec<PathBuf>,
+    pub imported: HashSet<PathBuf>,
+    pub profile: Option<Profile>,
+    pub rng_state: u64,
+    pub strict: bool,
+    deadline: Option<Instant>,
+    deferred: Vec<Vec<Expr>>,
+}
+
+pub struct Scope<'a> {
+    interpreter: &'a mut Interpreter,
+    environment: &'a mut Environment,
+}
+
+impl Deref for Scope<'_> {
+    type Target = Interpreter;
+
+    fn deref(&self) -> &Self::Target {
+        self.interpreter
+    }
+}
+
+impl DerefMut for Scope<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.interpreter
+    }
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        *self.environment = self.interpreter.pop_scope();
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Default)]
+pub struct InterpreterBuilder {
+    output: Option<Rc<RefCell<dyn Write>>>,
+    input: Option<Rc<RefCell<dyn BufRead>>>,
+    step_budget: Option<u64>,
+    max_call_depth: Option<usize>,
+    strict: bool,
+    seed: Option<u64>,
+}
+
+impl InterpreterBuilder {
+    pub fn output(mut self, output: Rc<RefCell<dyn Write>>) -> Self {
+        self.output = Some(output);
+        self
+    }
+
+    pub fn input(mut self, input: Rc<RefCell<dyn BufRead>>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    pub fn step_budget(mut self, step_budget: u64) -> Self {
+        self.step_budget = Some(step_budget);
+        self
+    }
+
+    pub fn max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = Some(max_call_depth);
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Interpreter {
+        let mut interpreter = Interpreter::new();
+        if let Some(output) = self.output {
+            interpreter.output = output;
+        }
+        if let Some(input) = self.input {
+            interpreter.input = input;
+        }
+        if let Some(seed) = self.seed {
+            interpreter.rng_state = seed;
+        }
+        if let Some(max_call_depth) = self.max_call_depth {
+            interpreter.max_call_depth = max_call_depth;
+        }
+        interpreter.step_budget = self.step_budget;
+        interpreter.strict = self.strict;
+
+        interpreter
+    }
 }
 
 impl Interpreter {
+    pub fn builder() -> InterpreterBuilder {
+        InterpreterBuilder::default()
+    }
+
     pub fn new() -> Self {
         let mut globals = Environment::new();
 
         let clock_closure = |_: &mut Interpreter, _: Vec<Evaluation>| {
-            let call = || {
-                let time = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as f64;
+            let time = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as f64;
+
+            Ok(Some(Evaluation::f64(time)))
+        };
+
+        let clock_callable = NativeFunction::new("clock".to_string(), 0, clock_closure);
+
+        globals.define(
+            "clock".to_string(),
+            Some(Evaluation::callable(Box::new(clock_callable))),
+        );
+
+        let env_depth_closure = |interpreter: &mut Interpreter, _: Vec<Evaluation>| {
+            let mut depth = 0;
+            let mut environment = &interpreter.environment;
+            while let Some(enclosing) = &environment.enclosing {
+                depth += 1;
+                environment = enclosing;
+            }
+
+            Ok(Some(Evaluation::f64(depth as f64)))
+        };
+
+        let env_depth_callable = NativeFunction::new("env_depth".to_string(), 0, env_depth_closure);
+
+        globals.define(
+            "env_depth".to_string(),
+            Some(Evaluation::callable(Box::new(env_depth_callable))),
+        );
+
+        // Reads one line without its line ending, or nil at the end of input.
+        let input_closure = |interpreter: &mut Interpreter, _: Vec<Evaluation>| {
+            let mut line = String::new();
+            let read = interpreter
+                .input
+                .borrow_mut()
+                .read_line(&mut line)
+                .map_err(|err| InterpretError::RuntimeError {
+                    err: err.to_string(),
+                })?;
+            if read == 0 {
+                return Ok(Some(Evaluation::nil(())));
+            }
+
+            let line = line.strip_suffix('\n').unwrap_or(&line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            Ok(Some(Evaluation::string(line.to_string())))
+        };
+
+        let input_callable = NativeFunction::new("input".to_string(), 0, input_closure);
+
+        globals.define(
+            "input".to_string(),
+            Some(Evaluation::callable(Box::new(input_callable))),
+        );
+
+        let assert_eq_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| {
+            let (actual, expected) = (&arguments[0], &arguments[1]);
+
+            if actual != expected {
+                return Err(InterpretError::RuntimeError {
+                    err: format!("assert_eq failed: {actual} != {expected}"),
+                });
+            }
+
+            Ok(None)
+        };
+
+        let assert_eq_callable = NativeFunction::new("assert_eq".to_string(), 2, assert_eq_closure);
+
+        globals.define(
+            "assert_eq".to_string(),
+            Some(Evaluation::callable(Box::new(assert_eq_callable))),
+        );
+
+        // f64::from_str always uses '.' as the decimal separator, whatever the system
+        // locale, so "1,5" is rejected. Surrounding whitespace is ignored.
+        let num_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::f64(number) => Ok(Some(Evaluation::f64(*number))),
+            Evaluation::string(string) => string
+                .trim()
+                .parse::<f64>()
+                .map(|number| Some(Evaluation::f64(number)))
+                .map_err(|_| InterpretError::RuntimeError {
+                    err: format!("Could not convert '{string}' to a number."),
+                }),
+            other => Err(InterpretError::RuntimeError {
+                err: format!("Could not convert '{other}' to a number."),
+            }),
+        };
+
+        let num_callable = NativeFunction::new("num".to_string(), 1, num_closure);
+
+        globals.define(
+            "num".to_string(),
+            Some(Evaluation::callable(Box::new(num_callable))),
+        );
+
+        let min_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::f64(a), Evaluation::f64(b)) => Ok(Some(Evaluation::f64(a.min(*b)))),
+            (a, b) => Err(InterpretError::RuntimeError {
+                err: format!("min expects two numbers, got {a} and {b}."),
+            }),
+        };
+
+        let min_callable = NativeFunction::new("min".to_string(), 2, min_closure);
+
+        globals.define(
+            "min".to_string(),
+            Some(Evaluation::callable(Box::new(min_callable))),
+        );
+
+        let max_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::f64(a), Evaluation::f64(b)) => Ok(Some(Evaluation::f64(a.max(*b)))),
+            (a, b) => Err(InterpretError::RuntimeError {
+                err: format!("max expects two numbers, got {a} and {b}."),
+            }),
+        };
+
+        let max_callable = NativeFunction::new("max".to_string(), 2, max_closure);
+
+        globals.define(
+            "max".to_string(),
+            Some(Evaluation::callable(Box::new(max_callable))),
+        );
+
+        let clamp_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+            &arguments[2],
+        ) {
+            (Evaluation::f64(x), Evaluation::f64(lo), Evaluation::f64(hi)) => {
+                if lo > hi || lo.is_nan() || hi.is_nan() {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!(
+                            "clamp lower bound {} is greater than upper bound {}.",
+                            arguments[1], arguments[2]
+                        ),
+                    });
+                }
+
+                Ok(Some(Evaluation::f64(x.clamp(*lo, *hi))))
+            }
+            (x, lo, hi) => Err(InterpretError::RuntimeError {
+                err: format!("clamp expects three numbers, got {x}, {lo} and {hi}."),
+            }),
+        };
+
+        let clamp_callable = NativeFunction::new("clamp".to_string(), 3, clamp_closure);
+
+        globals.define(
+            "clamp".to_string(),
+            Some(Evaluation::callable(Box::new(clamp_callable))),
+        );
+
+        // Halfway cases round away from zero, so round(2.5, 0) is 3 and round(-2.5, 0) is -3.
+        let round_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::f64(x), Evaluation::f64(digits)) => {
+                if *digits < 0.0 || digits.fract() != 0.0 {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!(
+                            "round digits must be a non-negative integer, got {}.",
+                            arguments[1]
+                        ),
+                    });
+                }
+
+                // Past f64 precision the scaled value overflows, and x is already exact.
+                let factor = 10f64.powf(*digits);
+                if !(x * factor).is_finite() {
+                    return Ok(Some(Evaluation::f64(*x)));
+                }
+                Ok(Some(Evaluation::f64((x * factor).round() / factor)))
+            }
+            (x, digits) => Err(InterpretError::RuntimeError {
+                err: format!("round expects two numbers, got {x} and {digits}."),
+            }),
+        };
+
+        let round_callable = NativeFunction::new("round".to_string(), 2, round_closure);
+
+        globals.define(
+            "round".to_string(),
+            Some(Evaluation::callable(Box::new(round_callable))),
+        );
+
+        let fmt_num_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::f64(x), Evaluation::f64(digits)) => {
+                if *digits < 0.0 || digits.fract() != 0.0 {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!(
+                            "fmtNum digits must be a non-negative integer, got {}.",
+                            arguments[1]
+                        ),
+                    });
+                }
+                if *digits > MAX_FMT_NUM_DIGITS as f64 {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!(
+                            "fmtNum digits must be at most {MAX_FMT_NUM_DIGITS}, got {}.",
+                            arguments[1]
+                        ),
+                    });
+                }
+
+                Ok(Some(Evaluation::string(format!(
+                    "{:.*}",
+                    *digits as usize, x
+                ))))
+            }
+            (x, digits) => Err(InterpretError::RuntimeError {
+                err: format!("fmtNum expects two numbers, got {x} and {digits}."),
+            }),
+        };
+
+        let fmt_num_callable = NativeFunction::new("fmtNum".to_string(), 2, fmt_num_closure);
+
+        globals.define(
+            "fmtNum".to_string(),
+            Some(Evaluation::callable(Box::new(fmt_num_callable))),
+        );
+
+        let trim_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::string(string) => Ok(Some(Evaluation::string(string.trim().to_string()))),
+            other => Err(InterpretError::RuntimeError {
+                err: format!("trim expects a string, got {other}."),
+            }),
+        };
+
+        let trim_callable = NativeFunction::new("trim".to_string(), 1, trim_closure);
+
+        globals.define(
+            "trim".to_string(),
+            Some(Evaluation::callable(Box::new(trim_callable))),
+        );
+
+        let upper_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::string(string) => Ok(Some(Evaluation::string(string.to_uppercase()))),
+            other => Err(InterpretError::RuntimeError {
+                err: format!("upper expects a string, got {other}."),
+            }),
+        };
+
+        let upper_callable = NativeFunction::new("upper".to_string(), 1, upper_closure);
+
+        globals.define(
+            "upper".to_string(),
+            Some(Evaluation::callable(Box::new(upper_callable))),
+        );
+
+        let lower_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::string(string) => Ok(Some(Evaluation::string(string.to_lowercase()))),
+            other => Err(InterpretError::RuntimeError {
+                err: format!("lower expects a string, got {other}."),
+            }),
+        };
+
+        let lower_callable = NativeFunction::new("lower".to_string(), 1, lower_closure);
+
+        globals.define(
+            "lower".to_string(),
+            Some(Evaluation::callable(Box::new(lower_callable))),
+        );
+
+        let replace_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+            &arguments[2],
+        ) {
+            (Evaluation::string(string), Evaluation::string(from), Evaluation::string(to)) => {
+                Ok(Some(Evaluation::string(string.replace(from, to))))
+            }
+            (string, from, to) => Err(InterpretError::RuntimeError {
+                err: format!("replace expects three strings, got {string}, {from} and {to}."),
+            }),
+        };
+
+        let replace_callable = NativeFunction::new("replace".to_string(), 3, replace_closure);
+
+        globals.define(
+            "replace".to_string(),
+            Some(Evaluation::callable(Box::new(replace_callable))),
+        );
+
+        let contains_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::string(haystack), Evaluation::string(needle)) => {
+                Ok(Some(Evaluation::bool(haystack.contains(needle.as_str()))))
+            }
+            (haystack, needle) => Err(InterpretError::RuntimeError {
+                err: format!("contains expects two strings, got {haystack} and {needle}."),
+            }),
+        };
+
+        let contains_callable = NativeFunction::new("contains".to_string(), 2, contains_closure);
+
+        globals.define(
+            "contains".to_string(),
+            Some(Evaluation::callable(Box::new(contains_callable))),
+        );
+
+        let index_of_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::string(haystack), Evaluation::string(needle)) => {
+                let index = haystack
+                    .find(needle.as_str())
+                    .map(|byte| haystack[..byte].chars().count() as f64)
+                    .unwrap_or(-1.0);
+
+                Ok(Some(Evaluation::f64(index)))
+            }
+            (haystack, needle) => Err(InterpretError::RuntimeError {
+                err: format!("indexOf expects two strings, got {haystack} and {needle}."),
+            }),
+        };
+
+        let index_of_callable = NativeFunction::new("indexOf".to_string(), 2, index_of_closure);
+
+        globals.define(
+            "indexOf".to_string(),
+            Some(Evaluation::callable(Box::new(index_of_callable))),
+        );
+
+        let ord_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| {
+            if let Evaluation::string(string) = &arguments[0] {
+                let mut chars = string.chars();
+                if let (Some(char), None) = (chars.next(), chars.next()) {
+                    return Ok(Some(Evaluation::f64(char as u32 as f64)));
+                }
+            }
+
+            Err(InterpretError::RuntimeError {
+                err: format!(
+                    "ord expects a single-character string, got {}.",
+                    arguments[0]
+                ),
+            })
+        };
+
+        let ord_callable = NativeFunction::new("ord".to_string(), 1, ord_closure);
+
+        globals.define(
+            "ord".to_string(),
+            Some(Evaluation::callable(Box::new(ord_callable))),
+        );
+
+        let chr_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::f64(code)
+                if code.fract() == 0.0 && *code >= 0.0 && *code <= u32::MAX as f64 =>
+            {
+                match char::from_u32(*code as u32) {
+                    Some(char) => Ok(Some(Evaluation::string(char.to_string()))),
+                    None => Err(InterpretError::RuntimeError {
+                        err: format!("chr got an invalid code point {}.", arguments[0]),
+                    }),
+                }
+            }
+            other => Err(InterpretError::RuntimeError {
+                err: format!("chr expects an integer code point, got {other}."),
+            }),
+        };
+
+        let chr_callable = NativeFunction::new("chr".to_string(), 1, chr_closure);
+
+        globals.define(
+            "chr".to_string(),
+            Some(Evaluation::callable(Box::new(chr_callable))),
+        );
+
+        let exit_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| match &arguments[0] {
+            Evaluation::f64(code)
+                if code.fract() == 0.0 && *code >= i32::MIN as f64 && *code <= i32::MAX as f64 =>
+            {
+                Err(InterpretError::Exit { code: *code as i32 })
+            }
+            other => Err(InterpretError::RuntimeError {
+                err: format!("exit expects an integer code, got {other}."),
+            }),
+        };
+
+        let exit_callable = NativeFunction::new("exit".to_string(), 1, exit_closure);
+
+        globals.define(
+            "exit".to_string(),
+            Some(Evaluation::callable(Box::new(exit_callable))),
+        );
+
+        let to_json_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| {
+            let json = arguments[0].to_json()?;
+            Ok(Some(Evaluation::string(json.to_string())))
+        };
+
+        let to_json_callable = NativeFunction::new("toJson".to_string(), 1, to_json_closure);
+
+        globals.define(
+            "toJson".to_string(),
+            Some(Evaluation::callable(Box::new(to_json_callable))),
+        );
 
-                Evaluation::f64(time)
+        let from_json_closure = |_: &mut Interpreter, arguments: Vec<Evaluation>| {
+            let Evaluation::string(source) = &arguments[0] else {
+                return Err(InterpretError::RuntimeError {
+                    err: format!("fromJson expects a string, got {}.", arguments[0]),
+                });
             };
 
-            let display = || Evaluation::string(String::from("native <fn>"));
+            let json =
+                serde_json::from_str(source).map_err(|err| InterpretError::RuntimeError {
+                    err: format!("Invalid JSON: {err}."),
+                })?;
 
-            Some(Evaluation::nil(()))
+            Ok(Some(Evaluation::from_json(json)?))
         };
 
-        let clock_callable = NativeFunction::new("clock".to_string(), clock_closure);
+        let from_json_callable = NativeFunction::new("fromJson".to_string(), 1, from_json_closure);
 
         globals.define(
-            "clock".to_string(),
-            Some(Evaluation::callable(Box::new(clock_callable))),
+            "fromJson".to_string(),
+            Some(Evaluation::callable(Box::new(from_json_callable))),
         );
 
+        let random_closure = |interpreter: &mut Interpreter, _: Vec<Evaluation>| {
+            Ok(Some(Evaluation::f64(interpreter.next_random())))
+        };
+
+        let random_callable = NativeFunction::new("random".to_string(), 0, random_closure);
+
+        globals.define(
+            "random".to_string(),
+            Some(Evaluation::callable(Box::new(random_callable))),
+        );
+
+        let random_int_closure = |interpreter: &mut Interpreter, arguments: Vec<Evaluation>| match (
+            &arguments[0],
+            &arguments[1],
+        ) {
+            (Evaluation::f64(lo), Evaluation::f64(hi))
+                if lo.fract() == 0.0 && hi.fract() == 0.0 && lo <= hi =>
+            {
+                let offset = (interpreter.next_random() * (hi - lo + 1.0)).floor();
+                Ok(Some(Evaluation::f64(lo + offset)))
+            }
+            (lo, hi) => Err(InterpretError::RuntimeError {
+                err: format!("randomInt expects two integers with lo <= hi, got {lo} and {hi}."),
+            }),
+        };
+
+        let random_int_callable =
+            NativeFunction::new("randomInt".to_string(), 2, random_int_closure);
+
+        globals.define(
+            "randomInt".to_string(),
+            Some(Evaluation::callable(Box::new(random_int_callable))),
+        );
+
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as u64;
+
         Self {
-            globals,
-            environment: Environment::new(),
+            globals: globals.clone(),
+            environment: globals,
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            step_budget: None,
+            steps: 0,
+            output: Rc::new(RefCell::new(io::stdout())),
+            input: Rc::new(RefCell::new(io::BufReader::new(io::stdin()))),
+            import_stack: Vec::new(),
+            imported: HashSet::new(),
+            profile: None,
+            rng_state: seed,
+            strict: false,
+            deadline: None,
+            deferred: Vec::new(),
         }
     }
 
-    pub fn interpret(&mut self, statements: Vec<Stmt>) -> io::Result<()> {
-        statements
-            .into_iter()
-            .try_for_each(|mut stmt| self.stmt_execute(&mut stmt).and_then(|_| Ok(())))?;
+    pub fn interpret(&mut self, mut statements: Vec<Stmt>) -> Result<(), InterpretError> {
+        let result = self
+            .execute_statements(&mut statements)
+            .map_err(InterpretError::uncaught);
+        if let Err(InterpretError::Exit { .. }) = result {
+            let _ = self.output.borrow_mut().flush();
+        }
 
-        Ok(())
+        result.map(|_| ())
     }
 
-    pub fn stmt_execute(&mut self, stmt: &mut Stmt) -> Result<Option<Evaluation>, InterpretError> {
+    pub fn run_with_timeout(
+        &mut self,
+        statements: Vec<Stmt>,
+        timeout: Duration,
+    ) -> Result<(), InterpretError> {
+        self.deadline = Some(Instant::now() + timeout);
+        let result = self.interpret(statements);
+        self.deadline = None;
+
+        result
+    }
+
+    pub fn interpret_echo(
+        &mut self,
+        mut statements: Vec<Stmt>,
+    ) -> Result<Option<Evaluation>, InterpretError> {
+        let trailing = match statements.last() {
+            Some(Stmt::Expression(_)) => statements.pop(),
+            _ => None,
+        };
+
+        self.interpret(statements)?;
+
+        match trailing {
+            Some(Stmt::Expression(stmt)) => Ok(Some(
+                self.evaluate(stmt.expr).map_err(InterpretError::uncaught)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn stmt_execute(&mut self, stmt: &mut Stmt) -> Result<Flow, InterpretError> {
+        self.step()?;
+        if let Some(profile) = &mut self.profile {
+            profile.record((&*stmt).into());
+        }
+
         self.stmt_evaluate(stmt)
     }
 
-    pub fn stmt_evaluate(&mut self, stmt: &mut Stmt) -> Result<Option<Evaluation>, InterpretError> {
+    // SplitMix64, kept in-tree so a pinned `rng_state` gives the same sequence everywhere.
+    fn next_random(&mut self) -> f64 {
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Strict mode pins a variable to the type it was first given a value of.
+    fn check_strict_assign(&self, name: &Token, value: &Evaluation) -> Result<(), InterpretError> {
+        match self.environment.get_ref(name) {
+            Some(current) if current.type_name() != value.type_name() => {
+                Err(InterpretError::RuntimeError {
+                    err: format!(
+                        "Can't assign a {} to '{}', which holds a {}.",
+                        value.type_name(),
+                        name.lexeme,
+                        current.type_name()
+                    ),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn step(&mut self) -> Result<(), InterpretError> {
+        self.steps += 1;
+
+        // Reading the clock on every step would dominate tight loops.
+        if let Some(deadline) = self.deadline {
+            if self.steps.is_multiple_of(DEADLINE_CHECK_INTERVAL) && Instant::now() >= deadline {
+                return Err(InterpretError::LimitExceeded {
+                    err: "Execution timed out.".to_string(),
+                });
+            }
+        }
+
+        match self.step_budget {
+            Some(budget) if self.steps > budget => Err(InterpretError::LimitExceeded {
+                err: format!("Execution step budget of {budget} exceeded."),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn stmt_evaluate(&mut self, stmt: &mut Stmt) -> Result<Flow, InterpretError> {
         match stmt {
-            Stmt::Return(stmt) => {
-                let mut value = None;
-                if let Some(val) = &stmt.value {
-                    value = Some(self.evaluate(val.clone())?);
-                }
-                Ok(value)
+            Stmt::Return(stmt) => self.execute_return(stmt),
+            Stmt::Break(stmt) => Ok(Flow::Break(
+                stmt.label.as_ref().map(|label| label.lexeme.to_string()),
+            )),
+            Stmt::Continue(stmt) => Ok(Flow::Continue(
+                stmt.label.as_ref().map(|label| label.lexeme.to_string()),
+            )),
+            Stmt::While(stmt) => self.execute_while(stmt),
+            Stmt::For(stmt) => self.execute_for(stmt),
+            Stmt::If(stmt) => self.execute_if(stmt),
+            Stmt::Function(stmt) => self.define_function(stmt),
+            Stmt::Block(stmt) => {
+                self.stmt_execute_block(&mut stmt.statements, &mut Environment::new())
             }
-            Stmt::While(stmt) => {
-                while let Evaluation::bool(true) = self.evaluate(stmt.condition.clone())? {
-                    if let Some(eval) = self.execute_with_return(&mut stmt.body)? {
-                        return Ok(Some(eval));
-                    }
+            Stmt::Expression(stmt) => self.evaluate(stmt.expr.clone()).map(|_| Flow::Normal),
+            Stmt::Print(stmt) => self.write_output(&stmt.expr, "\n"),
+            Stmt::Write(stmt) => self.write_output(&stmt.expr, ""),
+            Stmt::Throw(stmt) => self.execute_throw(stmt),
+            Stmt::Try(stmt) => self.execute_try(stmt),
+            Stmt::Defer(stmt) => {
+                if let Some(deferred) = self.deferred.last_mut() {
+                    deferred.push(stmt.expr.clone());
                 }
-                Ok(None)
-            }
-            Stmt::If(stmt) => {
-                if let Evaluation::bool(truthy) = self.evaluate(stmt.condition.clone())? {
-                    if truthy {
-                        if let Some(eval) = self.execute_with_return(&mut stmt.then_branch)? {
-                            return Ok(Some(eval));
-                        }
-                    } else if let Some(_) = stmt.else_branch {
-                        if let Some(eval) =
-                            self.execute_with_return(&mut stmt.else_branch.clone().unwrap())?
-                        {
-                            return Ok(Some(eval));
-                        }
-                    }
-                };
-                Ok(None)
+                Ok(Flow::Normal)
             }
-            Stmt::Function(stmt) => {
-                let function = RloxFunction::new(
-                    stmt.params.len() as u8,
-                    stmt.name.lexeme.to_string(),
-                    stmt.clone(),
-                    Box::new(self.environment.clone()),
-                );
+            Stmt::Expect(stmt) => self.execute_expect(stmt),
+            Stmt::Import(stmt) => self
+                .import(&stmt.path)
+                .map(|_| Flow::Normal)
+                .map_err(|err| err.at_line(stmt.path.line)),
+            Stmt::Var(var) => self.define_var(var).map(|_| Flow::Normal),
+            Stmt::VarMulti(stmt) => stmt
+                .declarations
+                .iter()
+                .try_for_each(|var| self.define_var(var))
+                .map(|_| Flow::Normal),
+        }
+    }
 
-                self.environment.define(
-                    stmt.name.lexeme.to_string(),
-                    Some(Evaluation::callable(Box::new(function))),
-                );
+    fn define_function(&mut self, stmt: &StmtFunction) -> Result<Flow, InterpretError> {
+        let function = RloxFunction::new(stmt.clone(), Box::new(self.environment.clone()));
 
-                Ok(None)
-            }
-            Stmt::Block(stmt) => {
-                let a = self.stmt_execute_block(&mut stmt.statements, &mut Environment::new())?;
-                Ok(a)
+        self.environment.define(
+            stmt.name.lexeme.to_string(),
+            Some(Evaluation::callable(Box::new(function))),
+        );
+
+        Ok(Flow::Normal)
+    }
+
+    fn execute_return(&mut self, stmt: &StmtReturn) -> Result<Flow, InterpretError> {
+        let value = match &stmt.value {
+            Some(val) => self.evaluate(val.clone())?,
+            None => Evaluation::nil(()),
+        };
+        Ok(Flow::Return(value))
+    }
+
+    fn execute_while(&mut self, stmt: &mut StmtWhile) -> Result<Flow, InterpretError> {
+        while self.evaluate(stmt.condition.clone())?.is_truthy() {
+            match self.execute_iteration(&mut stmt.body)? {
+                Flow::Break(label) if targets(&label, &stmt.label) => return Ok(Flow::Normal),
+                Flow::Continue(label) if targets(&label, &stmt.label) => {}
+                Flow::Normal => {}
+                flow => return Ok(flow),
             }
-            Stmt::Expression(expr) => {
-                let evl = self.evaluate(expr.clone())?;
-                Ok(None)
+        }
+
+        match &mut stmt.else_branch {
+            Some(else_branch) => self.stmt_execute(else_branch),
+            None => Ok(Flow::Normal),
+        }
+    }
+
+    fn execute_for(&mut self, stmt: &mut StmtFor) -> Result<Flow, InterpretError> {
+        let mut environment = Environment::new();
+        let mut scope = self.push_scope(&mut environment);
+
+        if let Some(initializer) = &mut stmt.initializer {
+            scope.stmt_execute(initializer)?;
+        }
+
+        // Unlike while-else, a for's else runs only when the body never did.
+        let mut ran = false;
+        while match &stmt.condition {
+            Some(condition) => scope.evaluate(condition.clone())?.is_truthy(),
+            None => true,
+        } {
+            ran = true;
+            match scope.execute_iteration(&mut stmt.body)? {
+                Flow::Break(label) if targets(&label, &stmt.label) => break,
+                Flow::Continue(label) if targets(&label, &stmt.label) => {}
+                Flow::Normal => {}
+                flow => return Ok(flow),
             }
-            Stmt::Print(value) => {
-                let value = self.evaluate(value.clone())?;
-                println!("{value}");
-                Ok(None)
+
+            if let Some(increment) = &stmt.increment {
+                scope.evaluate(increment.clone())?;
             }
-            Stmt::Var(var) => {
-                let a = var
-                    .initializer
-                    .as_mut()
-                    .map(|expr| self.evaluate(expr.clone()))
-                    .transpose()?;
+        }
+
+        match &mut stmt.else_branch {
+            Some(else_branch) if !ran => scope.stmt_execute(else_branch),
+            _ => Ok(Flow::Normal),
+        }
+    }
+
+    // Each iteration exits the loop body's scope, so defers in an unbraced
+    // body run then rather than with the enclosing block.
+    fn execute_iteration(&mut self, body: &mut Stmt) -> Result<Flow, InterpretError> {
+        self.deferred.push(Vec::new());
+        let result = self.stmt_execute(body);
+        self.run_deferred(result)
+    }
 
-                self.environment.define(var.name.lexeme.to_string(), a);
-                Ok(None)
+    fn execute_if(&mut self, stmt: &mut StmtIf) -> Result<Flow, InterpretError> {
+        if self.evaluate(stmt.condition.clone())?.is_truthy() {
+            self.stmt_execute(&mut stmt.then_branch)
+        } else if let Some(else_branch) = &mut stmt.else_branch {
+            self.stmt_execute(else_branch)
+        } else {
+            Ok(Flow::Normal)
+        }
+    }
+
+    fn write_output(&mut self, value: &Expr, terminator: &str) -> Result<Flow, InterpretError> {
+        let value = self.evaluate(value.clone())?;
+        let mut output = self.output.borrow_mut();
+        write!(output, "{value}{terminator}")
+            .and_then(|_| output.flush())
+            .map_err(|err| InterpretError::RuntimeError {
+                err: err.to_string(),
+            })?;
+        Ok(Flow::Normal)
+    }
+
+    fn execute_throw(&mut self, stmt: &StmtThrow) -> Result<Flow, InterpretError> {
+        let value = self.evaluate(stmt.value.clone())?;
+        Err(InterpretError::Thrown {
+            value,
+            line: stmt.keyword.line,
+        })
+    }
+
+    fn execute_try(&mut self, stmt: &mut StmtTry) -> Result<Flow, InterpretError> {
+        // Only thrown values are caught. Runtime errors wait for an error
+        // value with a .message field, which needs instances first.
+        let value = match self.stmt_execute_block(&mut stmt.body, &mut Environment::new()) {
+            Err(InterpretError::Thrown { value, .. }) => value,
+            flow => return flow,
+        };
+
+        let mut environment = Environment::new();
+        environment.define(stmt.catch_var.lexeme.to_string(), Some(value));
+        self.stmt_execute_block(&mut stmt.catch_body, &mut environment)
+    }
+
+    fn execute_expect(&mut self, stmt: &StmtExpect) -> Result<Flow, InterpretError> {
+        if !self.evaluate(stmt.expr.clone())?.is_truthy() {
+            return Err(InterpretError::RuntimeError {
+                err: format!("Expectation failed: {}", ast_print(stmt.expr.clone())),
             }
+            .at_line(stmt.line));
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn define_var(&mut self, var: &StmtVar) -> Result<(), InterpretError> {
+        let a = var
+            .initializer
+            .as_ref()
+            .map(|expr| self.evaluate(expr.clone()))
+            .transpose()?;
+
+        self.environment.define(var.name.lexeme.to_string(), a);
+        Ok(())
+    }
+
+    fn import(&mut self, path: &Token) -> Result<(), InterpretError> {
+        let relative = path
+            .literal
+            .as_ref()
+            .map(|literal| literal.to_string())
+            .unwrap_or_default();
+        let import_error = |err: String| InterpretError::RuntimeError {
+            err: format!("Could not import '{relative}': {err}"),
+        };
+
+        let base = self
+            .import_stack
+            .last()
+            .and_then(|importer| importer.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let path =
+            fs::canonicalize(base.join(&relative)).map_err(|err| import_error(err.to_string()))?;
+
+        if self.import_stack.contains(&path) {
+            return Err(import_error("circular import.".to_string()));
         }
+
+        if !self.imported.insert(path.clone()) {
+            return Ok(());
+        }
+
+        let source = fs::read_to_string(&path).map_err(|err| import_error(err.to_string()))?;
+        let mut statements = crate::parse(source).map_err(|err| import_error(err.to_string()))?;
+
+        // Imported declarations land in the globals, so set aside any block
+        // scopes around the import while its statements run.
+        let mut scopes = Vec::new();
+        while self.environment.enclosing.is_some() {
+            scopes.push(self.pop_scope());
+        }
+
+        // Run the module without interpret()'s conversion of throws, so a
+        // thrown value reaches an enclosing try unchanged.
+        self.import_stack.push(path);
+        let result = self.execute_statements(&mut statements).map(|_| ());
+        self.import_stack.pop();
+
+        while let Some(mut scope) = scopes.pop() {
+            scope.enclosing = Some(Box::new(mem::take(&mut self.environment)));
+            self.environment = scope;
+        }
+
+        result
     }
 
     pub fn stmt_execute_block(
         &mut self,
-        statements: &mut Vec<Stmt>,
+        statements: &mut [Stmt],
         environment: &mut Environment,
-    ) -> Result<Option<Evaluation>, InterpretError> {
-        let previous = self.environment.clone();
-        self.environment = environment.clone();
-        self.environment.enclosing = Some(Box::new(previous));
+    ) -> Result<Flow, InterpretError> {
+        self.push_scope(environment).execute_statements(statements)
+    }
 
-        let a = statements
-            .iter_mut()
-            .find_map(|stmt| match self.stmt_execute(stmt) {
-                Ok(Some(eval)) => Some(Ok(eval)),
-                Ok(None) => None,
-                Err(err) => Some(Err(err)),
-            })
-            .transpose()?;
+    fn execute_statements(&mut self, statements: &mut [Stmt]) -> Result<Flow, InterpretError> {
+        self.deferred.push(Vec::new());
+        let result = self.execute_body(statements);
+        self.run_deferred(result)
+    }
 
-        *environment = self.environment.clone();
-        self.environment = *self.environment.enclosing.take().unwrap();
+    fn execute_body(&mut self, statements: &mut [Stmt]) -> Result<Flow, InterpretError> {
+        self.hoist_functions(statements)?;
+        for stmt in statements {
+            match self.stmt_execute(stmt)? {
+                Flow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
 
-        Ok(a)
+        Ok(Flow::Normal)
     }
 
-    fn execute_with_return(
+    // Pops the innermost scope's defers and runs them last-in, first-out. A
+    // failing defer only replaces a successful result.
+    fn run_deferred<T>(
         &mut self,
-        stmt: &mut Stmt,
-    ) -> Result<Option<Evaluation>, InterpretError> {
-        match self.stmt_execute(stmt)? {
-            Some(eval) => Ok(Some(eval)),
-            None => Ok(None),
+        mut result: Result<T, InterpretError>,
+    ) -> Result<T, InterpretError> {
+        let deferred = self.deferred.pop().unwrap_or_default();
+        for expr in deferred.into_iter().rev() {
+            let deferred_result = self.evaluate(expr);
+            if let (Ok(_), Err(err)) = (&result, deferred_result) {
+                result = Err(err);
+            }
+        }
+
+        result
+    }
+
+    pub fn push_scope<'a>(&'a mut self, environment: &'a mut Environment) -> Scope<'a> {
+        let enclosing = mem::take(&mut self.environment);
+        self.environment = mem::take(environment);
+        self.environment.enclosing = Some(Box::new(enclosing));
+
+        Scope {
+            interpreter: self,
+            environment,
         }
     }
 
+    pub fn pop_scope(&mut self) -> Environment {
+        let enclosing = self
+            .environment
+            .enclosing
+            .take()
+            .expect("pop_scope called without a pushed scope");
+
+        mem::replace(&mut self.environment, *enclosing)
+    }
+
+    fn hoist_functions(&mut self, statements: &mut [Stmt]) -> Result<(), InterpretError> {
+        statements
+            .iter_mut()
+            .filter(|stmt| matches!(stmt, Stmt::Function(_)))
+            .try_for_each(|stmt| self.stmt_evaluate(stmt).map(|_| ()))
+    }
+
     pub fn evaluate(&mut self, expr: Expr) -> Result<Evaluation, InterpretError> {
+        self.step()?;
+        if let Some(profile) = &mut self.profile {
+            profile.record((&expr).into());
+        }
+
         match expr {
-            Expr::Assign(expr) => {
-                let value = self.evaluate(*expr.value)?;
-                self.environment.assign(&expr.name, value.clone())?;
-                Ok(value)
-            }
+            Expr::Assign(expr) => self.evaluate_assign(expr),
             Expr::Literal(expr) => Ok(expr.literal.clone().into()),
+            Expr::Block(expr) => self.evaluate_block(expr),
             Expr::Call(expr) => {
-                let callee = if let Expr::Var(expr_var) = *expr.callee.clone() {
-                    expr_var.name.lexeme
-                } else {
-                    println!("Invalid callee type");
-                    exit(22)
-                };
-                let callee_evaluated = self.evaluate(*expr.callee.clone())?;
-
-                let mut arguments = Vec::new();
-                expr.arguments.into_iter().try_for_each(|arg| {
-                    arguments.push(self.evaluate(arg)?);
-                    Ok::<(), InterpretError>(())
-                })?;
+                let line = expr.paren.line;
+                self.evaluate_call(expr).map_err(|err| err.at_line(line))
+            }
+            Expr::Grouping(expr_grouping) => self.evaluate(*expr_grouping.expr),
+            Expr::Logical(expr) => self.evaluate_logical(expr),
+            Expr::Unary(expr) => self.evaluate_unary(expr),
+            Expr::Binary(expr) => self.evaluate_binary(expr),
+            Expr::Var(expr) => self
+                .environment
+                .get(&expr.name)
+                .map_err(|err| err.at_line(expr.name.line)),
+        }
+    }
 
-                let mut function: Box<dyn Callable> = Result::from(callee_evaluated.clone())?;
+    fn evaluate_unary(&mut self, expr: ExprUnary) -> Result<Evaluation, InterpretError> {
+        let right = self.evaluate(*expr.right)?;
+        Self::apply_unary(right, expr.operator.token_type)
+            .map_err(|err| err.at_line(expr.operator.line))
+    }
 
-                let arity = function.arity().into();
+    fn evaluate_binary(&mut self, expr: ExprBinary) -> Result<Evaluation, InterpretError> {
+        let left = self.evaluate(*expr.left)?;
+        let right = self.evaluate(*expr.right)?;
+        Self::apply_binary(left, right, expr.operator.token_type)
+            .map_err(|err| err.at_line(expr.operator.line))
+    }
 
-                if arguments.len() != arity {
-                    return Err(InterpretError::RuntimeError {
-                        err: format!(
-                            "Expected '{}' arguments but got '{}'",
-                            arity,
-                            arguments.len()
-                        ),
-                    });
-                }
+    fn evaluate_assign(&mut self, expr: ExprAssign) -> Result<Evaluation, InterpretError> {
+        let value = self.evaluate(*expr.value)?;
+        if self.strict {
+            self.check_strict_assign(&expr.name, &value)
+                .map_err(|err| err.at_line(expr.name.line))?;
+        }
+        self.environment
+            .assign(&expr.name, value.clone())
+            .map_err(|err| err.at_line(expr.name.line))?;
+        Ok(value)
+    }
 
-                let a = function.call(self, callee, arguments)?;
+    fn evaluate_logical(&mut self, expr: ExprLogical) -> Result<Evaluation, InterpretError> {
+        let left = self.evaluate(*expr.left)?;
 
-                let a = match a {
-                    Some(eval) => eval,
-                    None => Evaluation::nil(()),
-                };
+        let truthy = left.is_truthy();
+        if (expr.operator.token_type == TokenType::Or && truthy)
+            || (expr.operator.token_type == TokenType::And && !truthy)
+        {
+            return Ok(left);
+        }
 
-                Ok(a)
-            }
-            Expr::Grouping(expr_grouping) => self.evaluate(*expr_grouping.expr),
-            Expr::Logical(expr) => {
-                let left = self.evaluate(*expr.left)?;
-
-                if let Evaluation::bool(b) = left {
-                    if (expr.operator.token_type == TokenType::Or && b)
-                        || (expr.operator.token_type == TokenType::And && !b)
-                    {
-                        return Ok(left);
-                    }
-                };
+        self.evaluate(*expr.right)
+    }
 
-                self.evaluate(*expr.right)
-            }
-            Expr::Unary(expr_unary) => {
-                let right = self.evaluate(*expr_unary.right)?;
-                Self::evaluate_unary(right, expr_unary.operator.token_type)
-            }
-            Expr::Binary(expr_binary) => {
-                let left = self.evaluate(*expr_binary.left)?;
-                let right = self.evaluate(*expr_binary.right)?;
-                Self::evaluate_binary(left, right, expr_binary.operator.token_type)
+    fn evaluate_block(&mut self, expr: ExprBlock) -> Result<Evaluation, InterpretError> {
+        let mut statements = expr.statements;
+        let mut environment = Environment::new();
+        let mut scope = self.push_scope(&mut environment);
+
+        // The trailing value is part of the block, so defers run after it.
+        scope.deferred.push(Vec::new());
+        let result = match scope.execute_body(&mut statements) {
+            Ok(Flow::Normal) => match expr.value {
+                Some(value) => scope.evaluate(*value),
+                None => Ok(Evaluation::nil(())),
+            },
+            Ok(_) => Err(InterpretError::RuntimeError {
+                err: "Can't leave a block expression with return, break or continue.".to_string(),
+            }),
+            Err(err) => Err(err),
+        };
+        scope.run_deferred(result)
+    }
+
+    fn evaluate_call(&mut self, expr: ExprCall) -> Result<Evaluation, InterpretError> {
+        let callee = match &*expr.callee {
+            Expr::Var(expr_var) => expr_var.name.lexeme.to_string(),
+            _ => {
+                return Err(InterpretError::RuntimeError {
+                    err: "Can only call functions.".to_string(),
+                })
             }
-            Expr::Var(expr) => self.environment.get(&expr.name),
-        }
+        };
+        let callee_evaluated = self.evaluate(*expr.callee)?;
+
+        let arguments = expr
+            .arguments
+            .into_iter()
+            .map(|arg| self.evaluate(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let keyword_arguments = expr
+            .keyword_arguments
+            .into_iter()
+            .map(|(name, arg)| Ok((name, self.evaluate(arg)?)))
+            .collect::<Result<Vec<_>, InterpretError>>()?;
+
+        let mut function: Box<dyn Callable> = Result::from(callee_evaluated)?;
+
+        let arguments = function.bind_arguments(arguments, keyword_arguments)?;
+
+        check_arity(function.as_ref(), arguments.len())?;
+
+        function
+            .call(self, callee, arguments)
+            .map(|value| value.unwrap_or(Evaluation::nil(())))
     }
 
-    fn evaluate_unary(
+    fn apply_unary(
         right: Evaluation,
         operator_type: TokenType,
     ) -> Result<Evaluation, InterpretError> {
         match (&right, operator_type) {
             (Evaluation::f64(n), TokenType::Minus) => Ok(Evaluation::f64(-n)),
-            (Evaluation::bool(b), TokenType::Bang) => Ok(Evaluation::bool(!b)),
-            (Evaluation::nil(()), TokenType::Bang) => Ok(Evaluation::bool(false)),
+            (_, TokenType::Bang) => Ok(Evaluation::bool(!right.is_truthy())),
+            (Evaluation::f64(n), TokenType::Tilde) => {
+                if n.fract() != 0.0 || n.abs() >= i64::MAX as f64 {
+                    return Err(InterpretError::RuntimeError {
+                        err: format!("Operand of '~' must be an integer, got {right}."),
+                    });
+                }
+
+                Ok(Evaluation::f64(!(*n as i64) as f64))
+            }
             _ => Err(InterpretError::EvaluateUnaryFail {
                 right_evaluation: right,
                 operator_type,
@@ -479,7 +1632,7 @@ impl Interpreter {
         }
     }
 
-    fn evaluate_binary(
+    fn apply_binary(
         left: Evaluation,
         right: Evaluation,
         operator_type: TokenType,
@@ -0,0 +1,109 @@
+pub mod ast;
+pub mod callable;
+pub mod environment;
+pub mod errors;
+pub mod expr;
+pub mod formatter;
+pub mod interpreter;
+pub mod lint;
+pub mod parser;
+pub mod profile;
+pub mod reserved;
+pub mod scanner;
+pub mod stmt;
+pub mod token;
+
+use std::{cell::RefCell, fs, io::Write, path::Path, rc::Rc};
+
+use errors::RloxError;
+use interpreter::{Evaluation, Interpreter};
+use parser::Parser;
+use scanner::{Scanner, ScannerBuilder};
+use stmt::Stmt;
+use token::TokenType;
+
+pub fn parse(source: String) -> Result<Vec<Stmt>, RloxError> {
+    parse_with(source, false)
+}
+
+pub fn parse_eval(source: String) -> Result<Vec<Stmt>, RloxError> {
+    parse_with(source, true)
+}
+
+fn parse_with(source: String, allow_trailing_expression: bool) -> Result<Vec<Stmt>, RloxError> {
+    let mut scanner = Scanner::new(source);
+    let (tokens, errors) = scanner.scan_tokens();
+
+    if scanner.had_errors() {
+        return Err(RloxError::Scan(errors));
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.allow_trailing_expression = allow_trailing_expression;
+    parser.parse().map_err(RloxError::Parse)
+}
+
+pub fn format_source(source: String) -> Result<String, RloxError> {
+    let mut scanner = ScannerBuilder::new(source.clone())
+        .keep_comments(true)
+        .build();
+    let (tokens, errors) = scanner.scan_tokens();
+
+    if scanner.had_errors() {
+        return Err(RloxError::Scan(errors));
+    }
+
+    let comments = tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::Comment)
+        .cloned()
+        .collect();
+    let statements = Parser::new(tokens).parse().map_err(RloxError::Parse)?;
+
+    Ok(formatter::format_with_comments(
+        &statements,
+        &source,
+        comments,
+    ))
+}
+
+pub fn eval_expr(source: &str) -> Result<Evaluation, RloxError> {
+    let mut scanner = Scanner::new(source.to_string());
+    let (tokens, errors) = scanner.scan_tokens();
+
+    if scanner.had_errors() {
+        return Err(RloxError::Scan(errors));
+    }
+
+    let expr = Parser::new(tokens)
+        .parse_expression()
+        .map_err(|err| RloxError::Parse(vec![err]))?;
+
+    Interpreter::new()
+        .evaluate(expr)
+        .map_err(RloxError::Runtime)
+}
+
+pub fn run(source: String, output: Rc<RefCell<dyn Write>>) -> Result<(), RloxError> {
+    let statements = parse(source)?;
+
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output;
+    interpreter
+        .interpret(statements)
+        .map_err(RloxError::Runtime)
+}
+
+pub fn run_file(path: &Path, output: Rc<RefCell<dyn Write>>) -> Result<(), RloxError> {
+    let source = fs::read_to_string(path).map_err(RloxError::Io)?;
+    let statements = parse(source)?;
+
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output;
+    interpreter
+        .import_stack
+        .push(fs::canonicalize(path).map_err(RloxError::Io)?);
+    interpreter
+        .interpret(statements)
+        .map_err(RloxError::Runtime)
+}
@@ -0,0 +1,204 @@
+use std::fmt::Display;
+
+use crate::{
+    expr::Expr,
+    stmt::{Stmt, StmtVar},
+    token::Token,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    UnusedVariable { name: String, line: usize },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::UnusedVariable { name, line } => {
+                write!(
+                    f,
+                    "[line {line}] Warning: Local variable '{name}' is never read."
+                )
+            }
+        }
+    }
+}
+
+pub fn lint(statements: &[Stmt]) -> Vec<Warning> {
+    let mut linter = Linter::default();
+    linter.stmts(statements);
+
+    linter.warnings
+}
+
+struct Local {
+    name: Token,
+    used: bool,
+}
+
+// Globals live outside `scopes`, so they are never reported.
+#[derive(Default)]
+struct Linter {
+    scopes: Vec<Vec<Local>>,
+    warnings: Vec<Warning>,
+}
+
+impl Linter {
+    fn begin_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn end_scope(&mut self) {
+        let locals = self.scopes.pop().unwrap_or_default();
+
+        self.warnings
+            .extend(locals.into_iter().filter(|local| !local.used).map(|local| {
+                Warning::UnusedVariable {
+                    name: local.name.lexeme,
+                    line: local.name.line,
+                }
+            }));
+    }
+
+    fn declare(&mut self, name: &Token, used: bool) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Local {
+                name: name.clone(),
+                used,
+            });
+        }
+    }
+
+    fn read(&mut self, name: &Token) {
+        let local = self.scopes.iter_mut().rev().find_map(|scope| {
+            scope
+                .iter_mut()
+                .rev()
+                .find(|local| local.name.lexeme == name.lexeme)
+        });
+
+        if let Some(local) = local {
+            local.used = true;
+        }
+    }
+
+    fn stmts(&mut self, statements: &[Stmt]) {
+        statements.iter().for_each(|stmt| self.stmt(stmt));
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Block(stmt) => {
+                self.begin_scope();
+                self.stmts(&stmt.statements);
+                self.end_scope();
+            }
+            Stmt::Expression(stmt) => self.expr(&stmt.expr),
+            Stmt::Print(stmt) => self.expr(&stmt.expr),
+            Stmt::Write(stmt) => self.expr(&stmt.expr),
+            Stmt::Return(stmt) => {
+                if let Some(value) = &stmt.value {
+                    self.expr(value);
+                }
+            }
+            Stmt::Var(stmt) => self.var(stmt),
+            Stmt::VarMulti(stmt) => stmt.declarations.iter().for_each(|var| self.var(var)),
+            Stmt::While(stmt) => {
+                self.expr(&stmt.condition);
+                self.stmt(&stmt.body);
+                if let Some(else_branch) = &stmt.else_branch {
+                    self.stmt(else_branch);
+                }
+            }
+            Stmt::For(stmt) => {
+                self.begin_scope();
+                if let Some(initializer) = &stmt.initializer {
+                    self.stmt(initializer);
+                }
+                if let Some(condition) = &stmt.condition {
+                    self.expr(condition);
+                }
+                if let Some(increment) = &stmt.increment {
+                    self.expr(increment);
+                }
+                self.stmt(&stmt.body);
+                if let Some(else_branch) = &stmt.else_branch {
+                    self.stmt(else_branch);
+                }
+                self.end_scope();
+            }
+            Stmt::Break(_) | Stmt::Continue(_) | Stmt::Import(_) => {}
+            Stmt::If(stmt) => {
+                self.expr(&stmt.condition);
+                self.stmt(&stmt.then_branch);
+                if let Some(else_branch) = &stmt.else_branch {
+                    self.stmt(else_branch);
+                }
+            }
+            Stmt::Function(stmt) => {
+                self.declare(&stmt.name, true);
+                self.begin_scope();
+                stmt.params
+                    .iter()
+                    .for_each(|param| self.declare(param, true));
+                self.stmts(&stmt.body);
+                self.end_scope();
+            }
+            Stmt::Expect(stmt) => self.expr(&stmt.expr),
+            Stmt::Defer(stmt) => self.expr(&stmt.expr),
+            Stmt::Throw(stmt) => self.expr(&stmt.value),
+            Stmt::Try(stmt) => {
+                self.begin_scope();
+                self.stmts(&stmt.body);
+                self.end_scope();
+
+                self.begin_scope();
+                self.declare(&stmt.catch_var, true);
+                self.stmts(&stmt.catch_body);
+                self.end_scope();
+            }
+        }
+    }
+
+    fn var(&mut self, stmt: &StmtVar) {
+        if let Some(initializer) = &stmt.initializer {
+            self.expr(initializer);
+        }
+        self.declare(&stmt.name, false);
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Assign(expr) => self.expr(&expr.value),
+            Expr::Binary(expr) => {
+                self.expr(&expr.left);
+                self.expr(&expr.right);
+            }
+            Expr::Logical(expr) => {
+                self.expr(&expr.left);
+                self.expr(&expr.right);
+            }
+            Expr::Block(expr) => {
+                self.begin_scope();
+                self.stmts(&expr.statements);
+                if let Some(value) = &expr.value {
+                    self.expr(value);
+                }
+                self.end_scope();
+            }
+            Expr::Call(expr) => {
+                self.expr(&expr.callee);
+                expr.arguments
+                    .iter()
+                    .for_each(|argument| self.expr(argument));
+                expr.keyword_arguments
+                    .iter()
+                    .for_each(|(_, value)| self.expr(value));
+            }
+            Expr::Grouping(expr) => self.expr(&expr.expr),
+            Expr::Literal(_) => {}
+            Expr::Unary(expr) => self.expr(&expr.right),
+            Expr::Var(expr) => self.read(&expr.name),
+        }
+    }
+}
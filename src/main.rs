@@ -1,53 +1,177 @@
-mod ast;
-mod callable;
-mod environment;
-mod errors;
-mod expr;
-mod interpreter;
-mod parser;
-mod reserved;
-mod scanner;
-mod stmt;
-mod token;
-
 use std::{
     env,
-    fs::File,
-    io::{self, Read},
+    fs::{self, File},
+    io::{self, IsTerminal, Read},
     process::exit,
+    thread,
+};
+
+use rlox::{
+    ast::stmt_print,
+    errors::{InterpretError, RloxError, EXIT_RUNTIME_ERROR},
+    formatter,
+    interpreter::Interpreter,
+    lint,
+    profile::Profile,
 };
 
-use interpreter::Interpreter;
-use parser::Parser;
-use scanner::Scanner;
+// Parsing and evaluating deeply nested source recurses. A large stack keeps
+// that from overflowing.
+const INTERPRETER_STACK_SIZE: usize = 256 * 1024 * 1024;
 
 fn main() -> io::Result<()> {
-    rlox_run()
+    thread::Builder::new()
+        .stack_size(INTERPRETER_STACK_SIZE)
+        .spawn(rlox_run)?
+        .join()
+        .unwrap_or_else(|_| exit(EXIT_RUNTIME_ERROR))
 }
 
 fn rlox_run() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        println!("Usage: *.lox");
-        exit(1);
+    let mut args = env::args().skip(1);
+
+    let mut source = None;
+    let mut echo = false;
+    let mut ast = false;
+    let mut check = false;
+    let mut ast_json = false;
+    let mut run_json = false;
+    let mut profile = false;
+    let mut fmt = false;
+    let mut strict = false;
+    let mut color = io::stderr().is_terminal();
+    let mut step_budget = None;
+    let mut seed = None;
+    let mut script_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--eval" => {
+                source = Some(args.next().unwrap_or_else(|| usage()));
+                echo = true;
+            }
+            "--ast" => ast = true,
+            "--check" => check = true,
+            "--ast-json" => ast_json = true,
+            "--run-json" => run_json = true,
+            "--profile" => profile = true,
+            "--fmt" => fmt = true,
+            "--strict" => strict = true,
+            "--color=always" => color = true,
+            "--color=never" => color = false,
+            "--color=auto" => color = io::stderr().is_terminal(),
+            "--max-steps" => {
+                let steps = args.next().unwrap_or_else(|| usage());
+                step_budget = Some(steps.parse::<u64>().unwrap_or_else(|_| usage()));
+            }
+            "--seed" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                seed = Some(value.parse::<u64>().unwrap_or_else(|_| usage()));
+            }
+            path => {
+                source =
+                    Some(file_open(path).unwrap_or_else(|err| report(RloxError::Io(err), color)));
+                script_path = Some(fs::canonicalize(path)?);
+            }
+        }
     }
 
-    let path = &args[1];
+    let source = source.unwrap_or_else(|| usage());
+    let fmt_source = (fmt && !(run_json || echo)).then(|| source.clone());
+
+    let statements = match run_json {
+        true => serde_json::from_str(&source)
+            .unwrap_or_else(|err| report(RloxError::Io(err.into()), color)),
+        false => match echo {
+            true => rlox::parse_eval(source),
+            false => rlox::parse(source),
+        }
+        .unwrap_or_else(|err| report(err, color)),
+    };
+
+    if !(fmt || ast || ast_json) {
+        for warning in lint::lint(&statements) {
+            eprintln!("{warning}");
+        }
+    }
 
-    let source = file_open(path)?;
+    if check {
+        return Ok(());
+    }
 
-    let mut scanner = Scanner::new(source);
-    scanner.scan_tokens();
+    if fmt {
+        let formatted = match fmt_source {
+            Some(source) => rlox::format_source(source).unwrap_or_else(|err| report(err, color)),
+            None => formatter::format(&statements),
+        };
+        print!("{formatted}");
+        return Ok(());
+    }
 
-    let mut parser = Parser::new(scanner.tokens);
-    let statements = parser.parse()?;
+    if ast_json {
+        let json = serde_json::to_string_pretty(&statements).map_err(io::Error::other)?;
+        println!("{json}");
+        return Ok(());
+    }
 
-    let mut interpreter = Interpreter::new();
-    interpreter.interpret(statements)?;
+    if ast {
+        statements
+            .iter()
+            .for_each(|stmt| println!("{}", stmt_print(stmt)));
+        return Ok(());
+    }
+
+    let mut builder = Interpreter::builder().strict(strict);
+    if let Some(step_budget) = step_budget {
+        builder = builder.step_budget(step_budget);
+    }
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+
+    let mut interpreter = builder.build();
+    interpreter.import_stack.extend(script_path);
+    if profile {
+        interpreter.profile = Some(Profile::default());
+    }
+
+    let result = match echo {
+        true => interpreter.interpret_echo(statements).map(|value| {
+            if let Some(value) = value {
+                println!("{value}");
+            }
+        }),
+        false => interpreter.interpret(statements),
+    };
+
+    if let Some(profile) = &interpreter.profile {
+        eprint!("{profile}");
+    }
+
+    match result {
+        Err(InterpretError::Exit { code }) => exit(code),
+        Err(err) => report(RloxError::Runtime(err), color),
+        Ok(()) => {}
+    }
 
     Ok(())
 }
 
+fn report(err: RloxError, color: bool) -> ! {
+    match color {
+        true => eprintln!("\x1b[31m{err}\x1b[0m"),
+        false => eprintln!("{err}"),
+    }
+    exit(err.exit_code());
+}
+
+fn usage() -> ! {
+    println!(
+        "Usage: rlox [--ast | --ast-json | --check | --fmt] [--max-steps <n>] [--seed <n>] [--profile] [--strict] [--color=always|never|auto] (*.lox | --run-json *.json | --eval <program>)"
+    );
+    exit(1);
+}
+
 fn file_open(path: &str) -> io::Result<String> {
     let mut file = File::open(path)?;
     let mut string = String::new();
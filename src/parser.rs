@@ -1,50 +1,90 @@
 use crate::{
     errors::ParseError,
-    expr::{Expr, ExprAssign, ExprBinary, ExprCall, ExprGrouping, ExprLiteral, ExprUnary, ExprVar},
-    stmt::{Stmt, StmtBlock, StmtFunction, StmtIf, StmtReturn, StmtVar, StmtWhile},
+    expr::{
+        Expr, ExprAssign, ExprBinary, ExprBlock, ExprCall, ExprGrouping, ExprLiteral, ExprLogical,
+        ExprUnary, ExprVar,
+    },
+    stmt::{
+        Stmt, StmtBlock, StmtBreak, StmtContinue, StmtDefer, StmtExpect, StmtExpression, StmtFor,
+        StmtFunction, StmtIf, StmtImport, StmtPrint, StmtReturn, StmtThrow, StmtTry, StmtVar,
+        StmtVarMulti, StmtWhile, StmtWrite,
+    },
     token::{Literal, Token, TokenType},
 };
 
 pub struct Parser {
     pub tokens: Vec<Token>,
     pub current: usize,
+    pub errors: Vec<ParseError>,
+    pub allow_trailing_expression: bool,
+    loop_depth: usize,
+    labels: Vec<String>,
+    in_unbraced_if: bool,
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        let tokens = tokens
+            .into_iter()
+            .filter(|token| token.token_type != TokenType::Comment)
+            .collect();
+
+        Self {
+            tokens,
+            current: 0,
+            errors: Vec::new(),
+            allow_trailing_expression: false,
+            loop_depth: 0,
+            labels: Vec::new(),
+            in_unbraced_if: false,
+        }
     }
 
-    pub fn parse(&mut self) -> Result<Vec<Stmt>, ParseError> {
+    pub fn parse(&mut self) -> Result<Vec<Stmt>, Vec<ParseError>> {
         let mut statements = Vec::new();
         while !self.is_at_end() {
-            statements.push(self.declaration())
+            if let Some(stmt) = self.declaration() {
+                statements.push(stmt);
+            }
         }
 
-        let stmts = statements
-            .into_iter()
-            .filter_map(|stmt| Some(stmt.unwrap()))
-            .collect();
+        if !self.errors.is_empty() {
+            return Err(std::mem::take(&mut self.errors));
+        }
 
-        Ok(stmts)
+        Ok(statements)
+    }
+
+    pub fn parse_expression(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.expression()?;
+        if !self.is_at_end() {
+            return Err(ParseError::ParseFail {
+                token: self.peek(),
+                message: "Expect end of expression.".to_string(),
+            });
+        }
+
+        Ok(expr)
     }
 
     fn declaration(&mut self) -> Option<Stmt> {
-        if self.match_token(&vec![TokenType::Var]) {
-            match self.var_declaration() {
-                Ok(stmt) => return Some(stmt),
-                Err(err) => {
-                    println!("Prior to synchronize: {}", err);
-                    self.synchronize();
-                    return None;
-                }
-            }
+        let start = self.peek().span.0;
+        let stmt = if self.match_token(&[TokenType::Var]) {
+            self.var_declaration()
+        } else {
+            self.statement()
         };
 
-        match self.statement() {
-            Ok(stmt) => Some(stmt),
+        match stmt {
+            Ok(stmt) => Some(stmt.with_span(self.span_from(start))),
             Err(err) => {
-                println!("Prior to synchronize: {}", err);
+                self.errors.push(err);
                 self.synchronize();
                 None
             }
@@ -52,11 +92,23 @@ impl Parser {
     }
 
     fn var_declaration(&mut self) -> Result<Stmt, ParseError> {
-        let name = self.consume(TokenType::Identifier, "Expect variable name.".to_string())?;
+        let mut declarations = Vec::new();
+        loop {
+            let name = self.consume(TokenType::Identifier, "Expect variable name.".to_string())?;
 
-        let mut initializer = None;
-        if self.match_token(&vec![TokenType::Equal]) {
-            initializer = Some(self.expression()?);
+            let mut initializer = None;
+            if self.match_token(&[TokenType::Equal]) {
+                initializer = Some(self.expression()?);
+            }
+
+            let start = name.span.0;
+            let mut declaration = StmtVar::new(name, initializer);
+            declaration.span = self.span_from(start);
+            declarations.push(declaration);
+
+            if !self.match_token(&[TokenType::Comma]) {
+                break;
+            }
         }
 
         self.consume(
@@ -64,29 +116,65 @@ impl Parser {
             "Expect ';' after variable declaration.".to_string(),
         )?;
 
-        Ok(Stmt::Var(StmtVar::new(name, initializer)))
+        match declarations.len() {
+            1 => Ok(Stmt::Var(declarations.remove(0))),
+            _ => Ok(Stmt::VarMulti(StmtVarMulti::new(declarations))),
+        }
     }
 
     fn statement(&mut self) -> Result<Stmt, ParseError> {
-        if self.match_token(&vec![TokenType::Fun]) {
+        let start = self.peek().span.0;
+        let stmt = self.unspanned_statement()?;
+
+        Ok(stmt.with_span(self.span_from(start)))
+    }
+
+    fn unspanned_statement(&mut self) -> Result<Stmt, ParseError> {
+        if self.peek().token_type == TokenType::Identifier
+            && self.peek_next().token_type == TokenType::Colon
+        {
+            return self.labeled_statement();
+        }
+        if self.match_token(&[TokenType::Fun]) {
             return self.function_statement("function".to_string());
         };
-        if self.match_token(&vec![TokenType::For]) {
-            return self.for_statement();
+        if self.match_token(&[TokenType::For]) {
+            return self.for_statement(None);
         };
-        if self.match_token(&vec![TokenType::Return]) {
+        if self.match_token(&[TokenType::Return]) {
             return self.return_statement();
         };
-        if self.match_token(&vec![TokenType::While]) {
-            return self.while_statement();
+        if self.match_token(&[TokenType::Break, TokenType::Continue]) {
+            return self.loop_control_statement();
+        };
+        if self.match_token(&[TokenType::While]) {
+            return self.while_statement(None);
         };
-        if self.match_token(&vec![TokenType::If]) {
+        if self.match_token(&[TokenType::If]) {
             return self.if_statement();
         };
-        if self.match_token(&vec![TokenType::Print]) {
+        if self.match_token(&[TokenType::Print]) {
             return self.print_statement();
         };
-        if self.match_token(&vec![TokenType::LeftBrace]) {
+        if self.match_token(&[TokenType::Write]) {
+            return self.write_statement();
+        };
+        if self.match_token(&[TokenType::Import]) {
+            return self.import_statement();
+        };
+        if self.match_token(&[TokenType::Expect]) {
+            return self.expect_statement();
+        };
+        if self.match_token(&[TokenType::Defer]) {
+            return self.defer_statement();
+        };
+        if self.match_token(&[TokenType::Throw]) {
+            return self.throw_statement();
+        };
+        if self.match_token(&[TokenType::Try]) {
+            return self.try_statement();
+        };
+        if self.match_token(&[TokenType::LeftBrace]) {
             return Ok(Stmt::Block(StmtBlock::new(self.block()?)));
         };
 
@@ -109,6 +197,90 @@ impl Parser {
         Ok(Stmt::Return(StmtReturn::new(keyword, value)))
     }
 
+    fn labeled_statement(&mut self) -> Result<Stmt, ParseError> {
+        let label = self.advance();
+        self.advance();
+
+        if self.match_token(&[TokenType::For]) {
+            return self.for_statement(Some(label));
+        }
+        if self.match_token(&[TokenType::While]) {
+            return self.while_statement(Some(label));
+        }
+
+        Err(ParseError::ParseFail {
+            token: self.peek(),
+            message: format!("Expect loop after label '{}'.", label.lexeme),
+        })
+    }
+
+    fn loop_control_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        if self.loop_depth == 0 {
+            return Err(ParseError::ParseFail {
+                message: format!("Can't use '{}' outside of a loop.", keyword.lexeme),
+                token: keyword,
+            });
+        }
+
+        let label = match self.match_token(&[TokenType::Identifier]) {
+            true => Some(self.previous()),
+            false => None,
+        };
+        if let Some(label) = &label {
+            if !self.labels.contains(&label.lexeme) {
+                return Err(ParseError::ParseFail {
+                    message: format!("Unknown loop label '{}'.", label.lexeme),
+                    token: label.clone(),
+                });
+            }
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            format!("Expect ';' after '{}'.", keyword.lexeme),
+        )?;
+
+        match keyword.token_type {
+            TokenType::Break => Ok(Stmt::Break(StmtBreak::new(keyword, label))),
+            _ => Ok(Stmt::Continue(StmtContinue::new(keyword, label))),
+        }
+    }
+
+    fn loop_body(&mut self, label: &Option<Token>) -> Result<Stmt, ParseError> {
+        self.loop_depth += 1;
+        self.labels
+            .extend(label.iter().map(|label| label.lexeme.to_string()));
+        let body = self.statement();
+        if label.is_some() {
+            self.labels.pop();
+        }
+        self.loop_depth -= 1;
+
+        body
+    }
+
+    // A loop only takes an `else` after a braced body. Otherwise the `else` in
+    // `if (a) while (b) c; else d;` would be taken from the enclosing if.
+    // In `if (a) while (b) { c; } else d;` the `else` could belong to either,
+    // so a loop else inside an unbraced if branch is an error.
+    fn loop_else_branch(&mut self, body: &Stmt) -> Result<Option<Stmt>, ParseError> {
+        if !matches!(body, Stmt::Block(_)) || self.peek().token_type != TokenType::Else {
+            return Ok(None);
+        }
+        if self.in_unbraced_if {
+            return Err(ParseError::ParseFail {
+                token: self.peek(),
+                message:
+                    "Ambiguous 'else' after a loop in an if; add braces around the if's branch."
+                        .to_string(),
+            });
+        }
+
+        self.advance();
+        Ok(Some(self.statement()?))
+    }
+
     fn function_statement(&mut self, kind: String) -> Result<Stmt, ParseError> {
         let name = self.consume(TokenType::Identifier, format!("Expect {} name.", kind))?;
         self.consume(
@@ -127,7 +299,7 @@ impl Parser {
                     self.consume(TokenType::Identifier, "Expect parameter name.".to_string())?,
                 );
 
-                if let false = self.match_token(&vec![TokenType::Comma]) {
+                if !self.match_token(&[TokenType::Comma]) || self.check(TokenType::RightParen)? {
                     break;
                 }
             }
@@ -143,20 +315,22 @@ impl Parser {
             format!("Expect '{{' before {} body.", kind),
         )?;
 
-        let body = self.block()?;
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let labels = std::mem::take(&mut self.labels);
+        let body = self.block();
+        self.loop_depth = loop_depth;
+        self.labels = labels;
+        let body = body?;
 
         Ok(Stmt::Function(StmtFunction::new(name, parameters, body)))
     }
 
-    fn for_statement(&mut self) -> Result<Stmt, ParseError> {
-        self.consume(
-            TokenType::LeftParen,
-            "Expect '(' after 'while'.".to_string(),
-        )?;
+    fn for_statement(&mut self, label: Option<Token>) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftParen, "Expect '(' after 'for'.".to_string())?;
 
-        let initializer = if self.match_token(&vec![TokenType::Semicolon]) {
+        let initializer = if self.match_token(&[TokenType::Semicolon]) {
             None
-        } else if self.match_token(&vec![TokenType::Var]) {
+        } else if self.match_token(&[TokenType::Var]) {
             Some(self.var_declaration()?)
         } else {
             Some(self.expression_statement()?)
@@ -184,24 +358,21 @@ impl Parser {
             "Expect ')' after for clauses.".to_string(),
         )?;
 
-        let mut body = self.statement()?;
+        let body = self.loop_body(&label)?;
 
-        if let Some(inc) = increment {
-            body = Stmt::Block(StmtBlock::new(vec![body, Stmt::Expression(inc)]))
-        }
-
-        if let Some(cnd) = condition {
-            body = Stmt::Block(StmtBlock::new(vec![Stmt::While(StmtWhile::new(cnd, body))]))
-        }
+        let else_branch = self.loop_else_branch(&body)?;
 
-        if let Some(ini) = initializer {
-            body = Stmt::Block(StmtBlock::new(vec![ini, body]))
-        }
-
-        Ok(body)
+        Ok(Stmt::For(StmtFor::new(
+            label,
+            initializer,
+            condition,
+            increment,
+            body,
+            else_branch,
+        )))
     }
 
-    fn while_statement(&mut self) -> Result<Stmt, ParseError> {
+    fn while_statement(&mut self, label: Option<Token>) -> Result<Stmt, ParseError> {
         self.consume(
             TokenType::LeftParen,
             "Expect '(' after 'while'.".to_string(),
@@ -212,9 +383,16 @@ impl Parser {
             "Expect ')' after condition.".to_string(),
         )?;
 
-        let body = self.statement()?;
+        let body = self.loop_body(&label)?;
+
+        let else_branch = self.loop_else_branch(&body)?;
 
-        Ok(Stmt::While(StmtWhile::new(condition, body)))
+        Ok(Stmt::While(StmtWhile::new(
+            label,
+            condition,
+            body,
+            else_branch,
+        )))
     }
 
     fn if_statement(&mut self) -> Result<Stmt, ParseError> {
@@ -222,10 +400,13 @@ impl Parser {
         let condition = self.expression()?;
         self.consume(TokenType::RightParen, "Expect ')' after 'if'.".to_string())?;
 
-        let then_branch = self.statement()?;
+        let in_unbraced_if = std::mem::replace(&mut self.in_unbraced_if, true);
+        let then_branch = self.statement();
+        self.in_unbraced_if = in_unbraced_if;
+        let then_branch = then_branch?;
         let mut else_branch = None;
 
-        if self.match_token(&vec![TokenType::Else]) {
+        if self.match_token(&[TokenType::Else]) {
             let a = self.statement()?;
             else_branch = Some(a);
         };
@@ -236,18 +417,99 @@ impl Parser {
     fn print_statement(&mut self) -> Result<Stmt, ParseError> {
         let value = self.expression()?;
         self.consume(TokenType::Semicolon, "Expect ';' after value.".to_string())?;
-        Ok(Stmt::Print(value))
+        Ok(Stmt::Print(StmtPrint::new(value)))
+    }
+
+    fn write_statement(&mut self) -> Result<Stmt, ParseError> {
+        let value = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expect ';' after value.".to_string())?;
+        Ok(Stmt::Write(StmtWrite::new(value)))
+    }
+
+    fn import_statement(&mut self) -> Result<Stmt, ParseError> {
+        let path = self.consume(
+            TokenType::String,
+            "Expect file path after 'import'.".to_string(),
+        )?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after import path.".to_string(),
+        )?;
+        Ok(Stmt::Import(StmtImport::new(path)))
+    }
+
+    fn expect_statement(&mut self) -> Result<Stmt, ParseError> {
+        let line = self.previous().line;
+        let expr = self.expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after expectation.".to_string(),
+        )?;
+        Ok(Stmt::Expect(StmtExpect::new(expr, line)))
+    }
+
+    fn defer_statement(&mut self) -> Result<Stmt, ParseError> {
+        let expr = self.expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after deferred expression.".to_string(),
+        )?;
+        Ok(Stmt::Defer(StmtDefer::new(expr)))
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expect ';' after thrown value.".to_string(),
+        )?;
+        Ok(Stmt::Throw(StmtThrow::new(keyword, value)))
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftBrace, "Expect '{' after 'try'.".to_string())?;
+        let body = self.block()?;
+
+        self.consume(
+            TokenType::Catch,
+            "Expect 'catch' after try block.".to_string(),
+        )?;
+        self.consume(
+            TokenType::LeftParen,
+            "Expect '(' after 'catch'.".to_string(),
+        )?;
+        let catch_var = self.consume(
+            TokenType::Identifier,
+            "Expect exception variable name.".to_string(),
+        )?;
+        self.consume(
+            TokenType::RightParen,
+            "Expect ')' after exception variable.".to_string(),
+        )?;
+
+        self.consume(
+            TokenType::LeftBrace,
+            "Expect '{' before catch body.".to_string(),
+        )?;
+        let catch_body = self.block()?;
+
+        Ok(Stmt::Try(StmtTry::new(body, catch_var, catch_body)))
     }
 
     fn block(&mut self) -> Result<Vec<Stmt>, ParseError> {
         let mut statements = Vec::new();
+        let in_unbraced_if = std::mem::take(&mut self.in_unbraced_if);
 
         while !self.is_at_end() {
             if self.peek().token_type == TokenType::RightBrace {
                 break;
             }
-            statements.push(self.declaration().unwrap())
+            if let Some(stmt) = self.declaration() {
+                statements.push(stmt);
+            }
         }
+        self.in_unbraced_if = in_unbraced_if;
 
         self.consume(TokenType::RightBrace, "Expect '}' after block.".to_string())?;
 
@@ -256,11 +518,15 @@ impl Parser {
 
     fn expression_statement(&mut self) -> Result<Stmt, ParseError> {
         let expr = self.expression()?;
+        if self.allow_trailing_expression && self.is_at_end() {
+            return Ok(Stmt::Expression(StmtExpression::new(expr)));
+        }
+
         self.consume(
             TokenType::Semicolon,
             "Expect ';' after expression.".to_string(),
         )?;
-        Ok(Stmt::Expression(expr))
+        Ok(Stmt::Expression(StmtExpression::new(expr)))
     }
 
     fn expression(&mut self) -> Result<Expr, ParseError> {
@@ -270,12 +536,13 @@ impl Parser {
     fn assignment(&mut self) -> Result<Expr, ParseError> {
         let expr = self.or()?;
 
-        if self.match_token(&vec![TokenType::Equal]) {
+        if self.match_token(&[TokenType::Equal]) {
             let equals = self.previous();
             let value = self.assignment()?;
 
-            if let Expr::Var(ExprVar { name }) = expr {
-                return Ok(Expr::Assign(ExprAssign::new(name, value)));
+            if let Expr::Var(ExprVar { name, span }) = expr {
+                let assign = Expr::Assign(ExprAssign::new(name, value));
+                return Ok(assign.with_span(self.span_from(span.0)));
             }
 
             return Err(ParseError::InvalidAssignmentTarget { token: equals });
@@ -285,17 +552,17 @@ impl Parser {
     }
 
     fn or(&mut self) -> Result<Expr, ParseError> {
-        self.binary_expr(Self::and, vec![TokenType::Or])
+        self.logical_expr(Self::and, TokenType::Or)
     }
 
     fn and(&mut self) -> Result<Expr, ParseError> {
-        self.binary_expr(Self::equality, vec![TokenType::And])
+        self.logical_expr(Self::equality, TokenType::And)
     }
 
     fn equality(&mut self) -> Result<Expr, ParseError> {
         self.binary_expr(
             Self::comparison,
-            vec![TokenType::Bang, TokenType::BangEqual],
+            vec![TokenType::BangEqual, TokenType::EqualEqual],
         )
     }
 
@@ -320,14 +587,17 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Expr, ParseError> {
-        self.unary_expr(Self::call, vec![TokenType::Bang, TokenType::Minus])
+        self.unary_expr(
+            Self::call,
+            vec![TokenType::Bang, TokenType::Minus, TokenType::Tilde],
+        )
     }
 
     fn call(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.primary()?;
 
         loop {
-            if self.match_token(&vec![TokenType::LeftParen]) {
+            if self.match_token(&[TokenType::LeftParen]) {
                 expr = self.finish_call(expr)?;
             } else {
                 break;
@@ -339,14 +609,30 @@ impl Parser {
 
     fn finish_call(&mut self, callee: Expr) -> Result<Expr, ParseError> {
         let mut arguments = Vec::new();
+        let mut keyword_arguments = Vec::new();
 
         if !self.check(TokenType::RightParen)? {
             loop {
-                if arguments.len() >= 255 {
+                if arguments.len() + keyword_arguments.len() >= 255 {
                     return Err(ParseError::ArgumentsOverflow { token: self.peek() });
                 }
-                arguments.push(self.expression()?);
-                if !self.match_token(&vec![TokenType::Comma]) {
+
+                if self.peek().token_type == TokenType::Identifier
+                    && self.peek_next().token_type == TokenType::Colon
+                {
+                    let name = self.advance();
+                    self.advance();
+                    keyword_arguments.push((name, self.expression()?));
+                } else if !keyword_arguments.is_empty() {
+                    return Err(ParseError::ParseFail {
+                        token: self.peek(),
+                        message: "Positional argument can't follow keyword arguments.".to_string(),
+                    });
+                } else {
+                    arguments.push(self.expression()?);
+                }
+
+                if !self.match_token(&[TokenType::Comma]) || self.check(TokenType::RightParen)? {
                     break;
                 }
             }
@@ -357,7 +643,10 @@ impl Parser {
             "Expect ')' after arguments.".to_string(),
         )?;
 
-        Ok(Expr::Call(ExprCall::new(callee, paren, arguments)))
+        let start = callee.span().0;
+        let call = Expr::Call(ExprCall::new(callee, paren, arguments, keyword_arguments));
+
+        Ok(call.with_span(self.span_from(start)))
     }
 
     fn primary(&mut self) -> Result<Expr, ParseError> {
@@ -365,53 +654,119 @@ impl Parser {
             return Err(ParseError::ParseEOF { token: self.peek() });
         }
 
-        let expr = match self.peek_consume().token_type {
+        let token = self.advance();
+        let start = token.span.0;
+        let expr = match token.token_type {
             TokenType::True => Expr::Literal(ExprLiteral::new(Literal::bool(true))),
             TokenType::False => Expr::Literal(ExprLiteral::new(Literal::bool(false))),
             TokenType::Nil => Expr::Literal(ExprLiteral::new(Literal::nil(()))),
-            TokenType::Number => Expr::Literal(ExprLiteral::new(self.previous().literal.unwrap())),
-            TokenType::String => Expr::Literal(ExprLiteral::new(self.previous().literal.unwrap())),
-            TokenType::Identifier => Expr::Var(ExprVar::new(self.previous())),
-            TokenType::Var => Expr::Var(ExprVar::new(self.peek())),
+            TokenType::Number | TokenType::String => match token.literal.clone() {
+                Some(literal) => Expr::Literal(ExprLiteral::new(literal)),
+                None => {
+                    return Err(ParseError::ParseFail {
+                        token,
+                        message: "Expect literal value.".to_string(),
+                    })
+                }
+            },
+            TokenType::Identifier => Expr::Var(ExprVar::new(token)),
             TokenType::LeftParen => {
                 let expr = self.expression()?;
-                let _ = self.consume(
+                self.consume(
                     TokenType::RightParen,
                     "Expect ')' after expression.".to_string(),
-                );
+                )?;
                 Expr::Grouping(ExprGrouping::new(expr))
             }
+            TokenType::LeftBrace => {
+                let in_unbraced_if = std::mem::take(&mut self.in_unbraced_if);
+                let block = self.block_expression();
+                self.in_unbraced_if = in_unbraced_if;
+                block?
+            }
             _ => {
                 return Err(ParseError::ParseFail {
-                    token: self.peek(),
+                    token,
                     message: "Expect expression.".to_string(),
                 });
             }
         };
 
-        Ok(expr)
+        Ok(expr.with_span(self.span_from(start)))
+    }
+
+    fn block_expression(&mut self) -> Result<Expr, ParseError> {
+        let mut statements = Vec::new();
+
+        loop {
+            if self.match_token(&[TokenType::RightBrace]) {
+                return Ok(Expr::Block(ExprBlock::new(statements, None)));
+            }
+
+            match self.peek().token_type {
+                TokenType::Var => {
+                    let start = self.advance().span.0;
+                    let stmt = self.var_declaration()?;
+                    statements.push(stmt.with_span(self.span_from(start)));
+                }
+                TokenType::Fun
+                | TokenType::For
+                | TokenType::Return
+                | TokenType::Break
+                | TokenType::Continue
+                | TokenType::While
+                | TokenType::If
+                | TokenType::Print
+                | TokenType::Write
+                | TokenType::Import
+                | TokenType::Expect
+                | TokenType::Defer
+                | TokenType::Throw
+                | TokenType::Try
+                | TokenType::LeftBrace => statements.push(self.statement()?),
+                _ => {
+                    let expr = self.expression()?;
+                    if self.match_token(&[TokenType::Semicolon]) {
+                        let span = self.span_from(expr.span().0);
+                        statements
+                            .push(Stmt::Expression(StmtExpression::new(expr)).with_span(span));
+                        continue;
+                    }
+
+                    self.consume(
+                        TokenType::RightBrace,
+                        "Expect ';' or '}' after expression.".to_string(),
+                    )?;
+                    return Ok(Expr::Block(ExprBlock::new(statements, Some(expr))));
+                }
+            }
+        }
     }
 
     fn synchronize(&mut self) {
-        let previous = self.advance();
+        self.advance();
 
         while !self.is_at_end() {
-            if previous.token_type == TokenType::Semicolon {
-                break;
+            if self.previous().token_type == TokenType::Semicolon {
+                return;
             }
 
             match self.peek().token_type {
-                TokenType::Class => {}
-                TokenType::Fun => {}
-                TokenType::Var => {}
-                TokenType::For => {}
-                TokenType::If => {}
-                TokenType::While => {}
-                TokenType::Print => {}
-                TokenType::Return => {
-                    break;
-                }
-                _ => unimplemented!(),
+                TokenType::Class
+                | TokenType::Fun
+                | TokenType::Var
+                | TokenType::For
+                | TokenType::If
+                | TokenType::While
+                | TokenType::Print
+                | TokenType::Write
+                | TokenType::Import
+                | TokenType::Expect
+                | TokenType::Defer
+                | TokenType::Throw
+                | TokenType::Try
+                | TokenType::Return => return,
+                _ => {}
             }
 
             self.advance();
@@ -433,7 +788,7 @@ impl Parser {
         })
     }
 
-    fn match_token(&mut self, token_types: &Vec<TokenType>) -> bool {
+    fn match_token(&mut self, token_types: &[TokenType]) -> bool {
         if self.is_at_end() {
             return false;
         }
@@ -468,18 +823,43 @@ impl Parser {
     }
 
     fn peek(&self) -> Token {
-        self.tokens.get(self.current).unwrap().clone()
+        self.token_at(self.current)
     }
 
-    fn peek_consume(&mut self) -> Token {
-        let current_token = self.tokens.get(self.current).unwrap().clone();
-        self.current += 1;
+    fn peek_next(&self) -> Token {
+        match self.tokens.get(self.current + 1) {
+            Some(token) => token.clone(),
+            None => self.peek(),
+        }
+    }
 
-        current_token
+    // From `start` through the end of the last consumed token.
+    fn span_from(&self, start: usize) -> (usize, usize) {
+        (start, self.previous().span.1)
     }
 
     fn previous(&self) -> Token {
-        self.tokens.get(self.current - 1).unwrap().clone()
+        match self.current.checked_sub(1) {
+            Some(index) => self.token_at(index),
+            None => self.eof(),
+        }
+    }
+
+    // A token stream that was truncated before its EOF reads as ending there.
+    fn token_at(&self, index: usize) -> Token {
+        match self.tokens.get(index) {
+            Some(token) => token.clone(),
+            None => self.eof(),
+        }
+    }
+
+    fn eof(&self) -> Token {
+        let (line, end) = self
+            .tokens
+            .last()
+            .map_or((1, 0), |token| (token.line, token.span.1));
+
+        Token::new(TokenType::EOF, String::new(), None, line, (end, end))
     }
 
     pub fn binary_expr(
@@ -492,7 +872,27 @@ impl Parser {
         while self.match_token(&match_types) {
             let operator = self.previous();
             let right = parse_fn(self)?;
+            let start = expr.span().0;
             expr = Expr::Binary(ExprBinary::new(expr, operator, right))
+                .with_span(self.span_from(start));
+        }
+
+        Ok(expr)
+    }
+
+    fn logical_expr(
+        &mut self,
+        parse_fn: fn(&mut Self) -> Result<Expr, ParseError>,
+        match_type: TokenType,
+    ) -> Result<Expr, ParseError> {
+        let mut expr = parse_fn(self)?;
+
+        while self.match_token(&[match_type]) {
+            let operator = self.previous();
+            let right = parse_fn(self)?;
+            let start = expr.span().0;
+            expr = Expr::Logical(ExprLogical::new(expr, operator, right))
+                .with_span(self.span_from(start));
         }
 
         Ok(expr)
@@ -506,7 +906,10 @@ impl Parser {
         if self.match_token(&match_types) {
             let operator = self.previous();
             let right = self.unary_expr(parse_fn, match_types)?;
-            return Ok(Expr::Unary(ExprUnary::new(operator, right)));
+            let start = operator.span.0;
+            return Ok(
+                Expr::Unary(ExprUnary::new(operator, right)).with_span(self.span_from(start))
+            );
         }
 
         parse_fn(self)
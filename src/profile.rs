@@ -0,0 +1,104 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{expr::Expr, stmt::Stmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NodeKind {
+    Block,
+    Expression,
+    Print,
+    Write,
+    Return,
+    Var,
+    While,
+    For,
+    Break,
+    Continue,
+    If,
+    Function,
+    Import,
+    Expect,
+    Defer,
+    Throw,
+    Try,
+
+    Assign,
+    Binary,
+    BlockExpr,
+    Call,
+    Grouping,
+    Literal,
+    Logical,
+    Unary,
+    Variable,
+}
+
+impl From<&Stmt> for NodeKind {
+    fn from(stmt: &Stmt) -> Self {
+        match stmt {
+            Stmt::Block(_) => NodeKind::Block,
+            Stmt::Expression(_) => NodeKind::Expression,
+            Stmt::Print(_) => NodeKind::Print,
+            Stmt::Write(_) => NodeKind::Write,
+            Stmt::Return(_) => NodeKind::Return,
+            Stmt::Var(_) | Stmt::VarMulti(_) => NodeKind::Var,
+            Stmt::While(_) => NodeKind::While,
+            Stmt::For(_) => NodeKind::For,
+            Stmt::Break(_) => NodeKind::Break,
+            Stmt::Continue(_) => NodeKind::Continue,
+            Stmt::If(_) => NodeKind::If,
+            Stmt::Function(_) => NodeKind::Function,
+            Stmt::Import(_) => NodeKind::Import,
+            Stmt::Expect(_) => NodeKind::Expect,
+            Stmt::Defer(_) => NodeKind::Defer,
+            Stmt::Throw(_) => NodeKind::Throw,
+            Stmt::Try(_) => NodeKind::Try,
+        }
+    }
+}
+
+impl From<&Expr> for NodeKind {
+    fn from(expr: &Expr) -> Self {
+        match expr {
+            Expr::Assign(_) => NodeKind::Assign,
+            Expr::Binary(_) => NodeKind::Binary,
+            Expr::Block(_) => NodeKind::BlockExpr,
+            Expr::Call(_) => NodeKind::Call,
+            Expr::Grouping(_) => NodeKind::Grouping,
+            Expr::Literal(_) => NodeKind::Literal,
+            Expr::Logical(_) => NodeKind::Logical,
+            Expr::Unary(_) => NodeKind::Unary,
+            Expr::Var(_) => NodeKind::Variable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub counts: HashMap<NodeKind, u64>,
+}
+
+impl Profile {
+    pub fn record(&mut self, kind: NodeKind) {
+        *self.counts.entry(kind).or_default() += 1;
+    }
+
+    pub fn count(&self, kind: NodeKind) -> u64 {
+        self.counts.get(&kind).copied().unwrap_or_default()
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut counts = self.counts.iter().collect::<Vec<_>>();
+        counts.sort_by(|(a_kind, a_count), (b_kind, b_count)| {
+            b_count.cmp(a_count).then(a_kind.cmp(b_kind))
+        });
+
+        for (kind, count) in counts {
+            writeln!(f, "{count:>10} {kind:?}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -8,21 +8,30 @@ lazy_static! {
     pub static ref RESERVED_KEYWORDS: HashMap<&'static str, TokenType> = {
         let mut map = HashMap::default();
         map.insert("and", TokenType::And);
+        map.insert("break", TokenType::Break);
+        map.insert("catch", TokenType::Catch);
         map.insert("class", TokenType::Class);
+        map.insert("continue", TokenType::Continue);
+        map.insert("defer", TokenType::Defer);
         map.insert("else", TokenType::Else);
+        map.insert("expect", TokenType::Expect);
         map.insert("false", TokenType::False);
         map.insert("for", TokenType::For);
         map.insert("fun", TokenType::Fun);
         map.insert("if", TokenType::If);
+        map.insert("import", TokenType::Import);
         map.insert("nil", TokenType::Nil);
         map.insert("or", TokenType::Or);
         map.insert("print", TokenType::Print);
         map.insert("return", TokenType::Return);
         map.insert("super", TokenType::Super);
         map.insert("this", TokenType::This);
+        map.insert("throw", TokenType::Throw);
         map.insert("true", TokenType::True);
+        map.insert("try", TokenType::Try);
         map.insert("var", TokenType::Var);
         map.insert("while", TokenType::While);
+        map.insert("write", TokenType::Write);
         map
     };
 }
@@ -1,39 +1,90 @@
-use std::process::exit;
-
 use crate::{
+    errors::ScanError,
     reserved::RESERVED_KEYWORDS,
     token::{Literal, Token, TokenType},
 };
 
 pub struct Scanner {
-    pub source: String,
+    pub source: Vec<char>,
     pub tokens: Vec<Token>,
+    pub errors: Vec<ScanError>,
+    pub keep_comments: bool,
 
     start: usize,
     line: usize,
     current: usize,
+    start_byte: usize,
+    current_byte: usize,
 }
 
-impl Scanner {
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+pub struct ScannerBuilder {
+    source: String,
+    keep_comments: bool,
+}
+
+impl ScannerBuilder {
     pub fn new(source: String) -> Self {
         Self {
             source,
+            keep_comments: false,
+        }
+    }
+
+    pub fn keep_comments(mut self, keep_comments: bool) -> Self {
+        self.keep_comments = keep_comments;
+        self
+    }
+
+    pub fn build(self) -> Scanner {
+        let mut scanner = Scanner::new(self.source);
+        scanner.keep_comments = self.keep_comments;
+
+        scanner
+    }
+}
+
+impl Scanner {
+    pub fn new(source: String) -> Self {
+        Self {
+            source: source.chars().collect(),
             tokens: Vec::new(),
+            errors: Vec::new(),
+            keep_comments: false,
 
             start: 0,
             current: 0,
             line: 1,
+            start_byte: 0,
+            current_byte: 0,
         }
     }
 
-    pub fn scan_tokens(&mut self) {
+    pub fn scan_tokens(&mut self) -> (Vec<Token>, Vec<ScanError>) {
         while !self.is_end() {
             self.start = self.current;
+            self.start_byte = self.current_byte;
             self._scan_tokens();
         }
 
-        self.tokens
-            .push(Token::new(TokenType::EOF, " ".to_string(), None, self.line))
+        self.tokens.push(Token::new(
+            TokenType::EOF,
+            " ".to_string(),
+            None,
+            self.line,
+            (self.current_byte, self.current_byte),
+        ));
+
+        (self.tokens.clone(), self.errors.clone())
+    }
+
+    pub fn had_errors(&self) -> bool {
+        !self.errors.is_empty()
     }
 
     fn _scan_tokens(&mut self) {
@@ -45,11 +96,13 @@ impl Scanner {
             '{' => self.add_token(TokenType::LeftBrace, None),
             '}' => self.add_token(TokenType::RightBrace, None),
             ',' => self.add_token(TokenType::Comma, None),
+            ':' => self.add_token(TokenType::Colon, None),
             '.' => self.add_token(TokenType::Dot, None),
             '-' => self.add_token(TokenType::Minus, None),
             '+' => self.add_token(TokenType::Plus, None),
             ';' => self.add_token(TokenType::Semicolon, None),
             '*' => self.add_token(TokenType::Star, None),
+            '~' => self.add_token(TokenType::Tilde, None),
 
             '!' => {
                 let token_type = match self.match_char('=') {
@@ -88,9 +141,18 @@ impl Scanner {
                     while self.peek() != '\n' && !self.is_end() {
                         self.advance();
                     }
-                };
+                    self.comment(self.line);
+                } else if self.match_char('*') {
+                    self.block_comment();
+                } else {
+                    self.add_token(TokenType::Slash, None)
+                }
+            }
 
-                self.add_token(TokenType::Slash, None)
+            '\\' if self.peek() == '\n' || (self.peek() == '\r' && self.peek_next() == '\n') => {
+                self.match_char('\r');
+                self.advance();
+                self.line += 1;
             }
 
             ' ' => {}
@@ -99,30 +161,56 @@ impl Scanner {
             '\n' => {
                 self.line += 1;
             }
-            '"' => {
-                self.string();
-                self.line += 1;
-            }
-
-            'o' => {
-                if self.match_char('r') {
-                    self.add_token(TokenType::Or, None)
-                }
-            }
+            '"' => self.string(),
 
             _ => {
-                if c.is_numeric() {
+                if c.is_ascii_digit() {
                     self.number();
-                } else if c.is_alphabetic() {
+                } else if c.is_alphabetic() || c == '_' {
                     self.identifier()
                 } else {
-                    println!("Unexpected token: {c}");
-                    exit(11)
+                    self.errors.push(ScanError::UnexpectedCharacter {
+                        character: c,
+                        line: self.line,
+                    });
                 }
             }
         }
     }
 
+    fn block_comment(&mut self) {
+        let line = self.line;
+
+        while !(self.peek() == '*' && self.peek_next() == '/') {
+            if self.is_end() {
+                self.errors
+                    .push(ScanError::UnterminatedComment { line: self.line });
+                return;
+            }
+            if self.advance() == '\n' {
+                self.line += 1;
+            }
+        }
+
+        self.advance();
+        self.advance();
+        self.comment(line);
+    }
+
+    fn comment(&mut self, line: usize) {
+        if !self.keep_comments {
+            return;
+        }
+
+        self.tokens.push(Token::new(
+            TokenType::Comment,
+            self.substring(self.start, self.current),
+            None,
+            line,
+            (self.start_byte, self.current_byte),
+        ));
+    }
+
     fn is_end(&self) -> bool {
         if self.source.len() <= self.current {
             return true;
@@ -131,17 +219,23 @@ impl Scanner {
     }
 
     fn advance(&mut self) -> char {
-        let char = self.source.chars().nth(self.current).unwrap();
+        let char = self.source[self.current];
         self.current += 1;
+        self.current_byte += char.len_utf8();
 
         char
     }
 
     fn add_token(&mut self, token_type: TokenType, literal: Option<Literal>) {
-        let lexeme = String::from(&self.source[self.start..self.current]);
-
-        self.tokens
-            .push(Token::new(token_type, lexeme, literal, self.line));
+        let lexeme = self.substring(self.start, self.current);
+
+        self.tokens.push(Token::new(
+            token_type,
+            lexeme,
+            literal,
+            self.line,
+            (self.start_byte, self.current_byte),
+        ));
     }
 
     fn match_char(&mut self, expected_char: char) -> bool {
@@ -149,11 +243,11 @@ impl Scanner {
             return false;
         }
 
-        if self.source.chars().nth(self.current).unwrap() != expected_char {
+        if self.source[self.current] != expected_char {
             return false;
         }
 
-        self.current += 1;
+        self.advance();
         true
     }
 
@@ -162,7 +256,7 @@ impl Scanner {
             return '\0';
         }
 
-        self.source.chars().nth(self.current).unwrap()
+        self.source[self.current]
     }
 
     fn peek_next(&self) -> char {
@@ -170,7 +264,7 @@ impl Scanner {
             return '\0';
         }
 
-        self.source.chars().nth(self.current + 1).unwrap()
+        self.source[self.current + 1]
     }
 
     fn string(&mut self) {
@@ -182,46 +276,56 @@ impl Scanner {
         }
 
         if self.is_end() {
-            println!("Unterminated string");
-            exit(12)
+            self.errors
+                .push(ScanError::UnterminatedString { line: self.line });
+            return;
         }
 
         self.advance();
 
-        let literal = String::from(&self.source[self.start + 1..self.current - 1]);
+        let literal = self
+            .substring(self.start + 1, self.current - 1)
+            .replace("\r\n", "\n");
         self.add_token(TokenType::String, Some(Literal::string(literal)));
     }
 
     fn number(&mut self) {
-        while self.peek().is_numeric() {
+        while self.peek().is_ascii_digit() {
             self.advance();
         }
 
-        if self.peek() == '.' && self.peek_next().is_numeric() {
+        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
             self.advance();
 
-            while self.peek().is_numeric() {
+            while self.peek().is_ascii_digit() {
                 self.advance();
             }
 
-            //TODO: return here as fsize
+            let literal = self
+                .substring(self.start, self.current)
+                .parse::<f64>()
+                .unwrap();
+
+            return self.add_token(TokenType::Number, Some(Literal::f64(literal)));
         };
 
-        let literal = (&self.source[self.start..self.current])
-            .parse::<f64>()
-            .unwrap();
+        let text = self.substring(self.start, self.current);
+        let literal = match text.parse::<i64>() {
+            Ok(int) => Literal::int(int),
+            Err(_) => Literal::f64(text.parse::<f64>().unwrap()),
+        };
 
-        self.add_token(TokenType::Number, Some(Literal::f64(literal)));
+        self.add_token(TokenType::Number, Some(literal));
     }
 
     fn identifier(&mut self) {
-        while self.peek().is_alphanumeric() {
+        while self.peek().is_alphanumeric() || self.peek() == '_' {
             self.advance();
         }
 
-        let text = &self.source[self.start..self.current];
+        let text = self.substring(self.start, self.current);
         let token_type = {
-            match RESERVED_KEYWORDS.get(text) {
+            match RESERVED_KEYWORDS.get(text.as_str()) {
                 Some(t) => *t,
                 None => TokenType::Identifier,
             }
@@ -229,4 +333,8 @@ impl Scanner {
 
         self.add_token(token_type, None);
     }
+
+    fn substring(&self, start: usize, end: usize) -> String {
+        self.source[start..end].iter().collect()
+    }
 }
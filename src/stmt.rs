@@ -1,77 +1,223 @@
-use crate::{expr::Expr, token::Token};
+use serde::{Deserialize, Serialize};
 
-type StmtExpression = Expr;
-type StmtPrint = Expr;
+use crate::{expr::Expr, token::Token};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Stmt {
     Block(StmtBlock),
     Expression(StmtExpression),
     Print(StmtPrint),
+    Write(StmtWrite),
     Return(StmtReturn),
     Var(StmtVar),
+    VarMulti(StmtVarMulti),
     While(StmtWhile),
+    For(StmtFor),
+    Break(StmtBreak),
+    Continue(StmtContinue),
     If(StmtIf),
     Function(StmtFunction),
+    Import(StmtImport),
+    Expect(StmtExpect),
+    Defer(StmtDefer),
+    Throw(StmtThrow),
+    Try(StmtTry),
 }
 
-impl Stmt {}
+impl Stmt {
+    pub fn span(&self) -> (usize, usize) {
+        match self {
+            Stmt::Block(stmt) => stmt.span,
+            Stmt::Expression(stmt) => stmt.span,
+            Stmt::Print(stmt) => stmt.span,
+            Stmt::Write(stmt) => stmt.span,
+            Stmt::Return(stmt) => stmt.span,
+            Stmt::Var(stmt) => stmt.span,
+            Stmt::VarMulti(stmt) => stmt.span,
+            Stmt::While(stmt) => stmt.span,
+            Stmt::For(stmt) => stmt.span,
+            Stmt::Break(stmt) => stmt.span,
+            Stmt::Continue(stmt) => stmt.span,
+            Stmt::If(stmt) => stmt.span,
+            Stmt::Function(stmt) => stmt.span,
+            Stmt::Import(stmt) => stmt.span,
+            Stmt::Expect(stmt) => stmt.span,
+            Stmt::Defer(stmt) => stmt.span,
+            Stmt::Throw(stmt) => stmt.span,
+            Stmt::Try(stmt) => stmt.span,
+        }
+    }
 
-#[derive(Debug, Clone)]
+    pub fn with_span(mut self, span: (usize, usize)) -> Self {
+        *self.span_mut() = span;
+        self
+    }
+
+    fn span_mut(&mut self) -> &mut (usize, usize) {
+        match self {
+            Stmt::Block(stmt) => &mut stmt.span,
+            Stmt::Expression(stmt) => &mut stmt.span,
+            Stmt::Print(stmt) => &mut stmt.span,
+            Stmt::Write(stmt) => &mut stmt.span,
+            Stmt::Return(stmt) => &mut stmt.span,
+            Stmt::Var(stmt) => &mut stmt.span,
+            Stmt::VarMulti(stmt) => &mut stmt.span,
+            Stmt::While(stmt) => &mut stmt.span,
+            Stmt::For(stmt) => &mut stmt.span,
+            Stmt::Break(stmt) => &mut stmt.span,
+            Stmt::Continue(stmt) => &mut stmt.span,
+            Stmt::If(stmt) => &mut stmt.span,
+            Stmt::Function(stmt) => &mut stmt.span,
+            Stmt::Import(stmt) => &mut stmt.span,
+            Stmt::Expect(stmt) => &mut stmt.span,
+            Stmt::Defer(stmt) => &mut stmt.span,
+            Stmt::Throw(stmt) => &mut stmt.span,
+            Stmt::Try(stmt) => &mut stmt.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtBlock {
     pub statements: Vec<Stmt>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtBlock {
     pub fn new(statements: Vec<Stmt>) -> Self {
-        Self { statements }
+        Self {
+            statements,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtExpression {
+    pub expr: Expr,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtExpression {
+    pub fn new(expr: Expr) -> Self {
+        Self { expr, span: (0, 0) }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtPrint {
+    pub expr: Expr,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtPrint {
+    pub fn new(expr: Expr) -> Self {
+        Self { expr, span: (0, 0) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtWrite {
+    pub expr: Expr,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtWrite {
+    pub fn new(expr: Expr) -> Self {
+        Self { expr, span: (0, 0) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtWhile {
+    pub label: Option<Token>,
     pub condition: Expr,
     pub body: Box<Stmt>,
+    pub else_branch: Option<Box<Stmt>>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtWhile {
-    pub fn new(condition: Expr, body: Stmt) -> Self {
+    pub fn new(
+        label: Option<Token>,
+        condition: Expr,
+        body: Stmt,
+        else_branch: Option<Stmt>,
+    ) -> Self {
         Self {
+            label,
             condition,
             body: Box::new(body),
+            else_branch: else_branch.map(Box::new),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtReturn {
     pub keyword: Token,
     pub value: Option<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtReturn {
     pub fn new(keyword: Token, value: Option<Expr>) -> Self {
-        Self { keyword, value }
+        Self {
+            keyword,
+            value,
+            span: (0, 0),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtVar {
     pub name: Token,
     pub initializer: Option<Expr>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtVar {
     pub fn new(name: Token, initializer: Option<Expr>) -> Self {
-        Self { name, initializer }
+        Self {
+            name,
+            initializer,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtVarMulti {
+    pub declarations: Vec<StmtVar>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtVarMulti {
+    pub fn new(declarations: Vec<StmtVar>) -> Self {
+        Self {
+            declarations,
+            span: (0, 0),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtIf {
     pub condition: Expr,
     pub then_branch: Box<Stmt>,
     pub else_branch: Option<Box<Stmt>>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtIf {
@@ -79,20 +225,179 @@ impl StmtIf {
         Self {
             condition,
             then_branch: Box::new(then_branch),
-            else_branch: else_branch.map(|stmt| Box::new(stmt)),
+            else_branch: else_branch.map(Box::new),
+            span: (0, 0),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StmtFunction {
     pub name: Token,
     pub params: Vec<Token>,
     pub body: Vec<Stmt>,
+    #[serde(default)]
+    pub span: (usize, usize),
 }
 
 impl StmtFunction {
     pub fn new(name: Token, params: Vec<Token>, body: Vec<Stmt>) -> Self {
-        Self { name, params, body }
+        Self {
+            name,
+            params,
+            body,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtImport {
+    pub path: Token,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtImport {
+    pub fn new(path: Token) -> Self {
+        Self { path, span: (0, 0) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtExpect {
+    pub expr: Expr,
+    pub line: usize,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtExpect {
+    pub fn new(expr: Expr, line: usize) -> Self {
+        Self {
+            expr,
+            line,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtFor {
+    pub label: Option<Token>,
+    pub initializer: Option<Box<Stmt>>,
+    pub condition: Option<Expr>,
+    pub increment: Option<Expr>,
+    pub body: Box<Stmt>,
+    pub else_branch: Option<Box<Stmt>>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtFor {
+    pub fn new(
+        label: Option<Token>,
+        initializer: Option<Stmt>,
+        condition: Option<Expr>,
+        increment: Option<Expr>,
+        body: Stmt,
+        else_branch: Option<Stmt>,
+    ) -> Self {
+        Self {
+            label,
+            initializer: initializer.map(Box::new),
+            condition,
+            increment,
+            body: Box::new(body),
+            else_branch: else_branch.map(Box::new),
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtBreak {
+    pub keyword: Token,
+    pub label: Option<Token>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtBreak {
+    pub fn new(keyword: Token, label: Option<Token>) -> Self {
+        Self {
+            keyword,
+            label,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtContinue {
+    pub keyword: Token,
+    pub label: Option<Token>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtContinue {
+    pub fn new(keyword: Token, label: Option<Token>) -> Self {
+        Self {
+            keyword,
+            label,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtDefer {
+    pub expr: Expr,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtDefer {
+    pub fn new(expr: Expr) -> Self {
+        Self { expr, span: (0, 0) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtThrow {
+    pub keyword: Token,
+    pub value: Expr,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtThrow {
+    pub fn new(keyword: Token, value: Expr) -> Self {
+        Self {
+            keyword,
+            value,
+            span: (0, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StmtTry {
+    pub body: Vec<Stmt>,
+    pub catch_var: Token,
+    pub catch_body: Vec<Stmt>,
+    #[serde(default)]
+    pub span: (usize, usize),
+}
+
+impl StmtTry {
+    pub fn new(body: Vec<Stmt>, catch_var: Token, catch_body: Vec<Stmt>) -> Self {
+        Self {
+            body,
+            catch_var,
+            catch_body,
+            span: (0, 0),
+        }
     }
 }
@@ -1,9 +1,14 @@
 use std::fmt::Display;
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_camel_case_types)]
 pub enum Literal {
     string(String),
+    // Only the scanner and the AST tell integer literals apart. Evaluation
+    // has a single number type, so at runtime every number is an f64.
+    int(i64),
     f64(f64),
     bool(bool),
     nil(()),
@@ -13,19 +18,21 @@ impl Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Literal::string(string) => write!(f, "{}", string),
-            Literal::f64(f64) => write!(f, "{}", f64),
+            Literal::int(int) => write!(f, "{}", int),
+            Literal::f64(f64) => write!(f, "{:?}", f64),
             Literal::bool(bool) => write!(f, "{}", bool),
             Literal::nil(_) => write!(f, "nil"),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     pub token_type: TokenType,
     pub lexeme: String,
     pub literal: Option<Literal>,
     pub line: usize,
+    pub span: (usize, usize),
 }
 
 impl Token {
@@ -34,22 +41,26 @@ impl Token {
         lexeme: String,
         literal: Option<Literal>,
         line: usize,
+        span: (usize, usize),
     ) -> Self {
         Self {
             token_type,
             lexeme,
             literal,
             line,
+            span,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum TokenType {
     LeftParen,
     RightParen,
     LeftBrace,
     RightBrace,
+    Colon,
     Comma,
     Dot,
     Minus,
@@ -57,6 +68,7 @@ pub enum TokenType {
     Semicolon,
     Slash,
     Star,
+    Tilde,
 
     Bang,
     BangEqual,
@@ -70,23 +82,33 @@ pub enum TokenType {
     Identifier,
     String,
     Number,
+    Comment,
 
     And,
+    Break,
+    Catch,
     Class,
+    Continue,
+    Defer,
     Else,
+    Expect,
     False,
     Fun,
     For,
     If,
+    Import,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
+    Write,
 
     EOF,
 }
@@ -0,0 +1,33 @@
+use rlox::{
+    environment::Environment,
+    interpreter::{Callable, RloxFunction},
+    stmt::Stmt,
+};
+
+#[test]
+fn function_arity_is_its_parameter_count() {
+    let Some(Stmt::Function(declaration)) =
+        rlox::parse("fun f(a, b) {}".to_string()).unwrap().pop()
+    else {
+        panic!("expected a function declaration");
+    };
+
+    let function = RloxFunction::new(declaration, Box::new(Environment::new()));
+
+    assert_eq!(function.arity(), 2);
+    assert_eq!(function.min_arity(), 2);
+}
+
+#[test]
+fn calling_with_the_wrong_argument_count_errors() {
+    let err = rlox::run(
+        "fun f(a, b) {}\nf(1);".to_string(),
+        std::rc::Rc::new(std::cell::RefCell::new(Vec::new())),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "[line 2] Expected '2' arguments but got '1'"
+    );
+}
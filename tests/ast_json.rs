@@ -0,0 +1,38 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::{interpreter::Interpreter, stmt::Stmt};
+
+const PROGRAM: &str = r#"
+fun fib(n) {
+  if (n < 2) return n;
+  return fib(n - 1) + fib(n - 2);
+}
+
+var i = 0;
+while (i < 5) {
+  print fib(i) + 0.5;
+  i = i + 1;
+}
+print "done" + "!";
+"#;
+
+fn interpret(statements: Vec<Stmt>) -> String {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output.clone();
+    interpreter.interpret(statements).unwrap();
+
+    let output = output.borrow().clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn json_round_trip_interprets_like_source() {
+    let statements = rlox::parse(PROGRAM.to_string()).unwrap();
+
+    let json = serde_json::to_string(&statements).unwrap();
+    let from_json = serde_json::from_str::<Vec<Stmt>>(&json).unwrap();
+
+    assert_eq!(interpret(from_json), interpret(statements));
+}
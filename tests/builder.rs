@@ -0,0 +1,33 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::{interpreter::Interpreter, parser::Parser, scanner::ScannerBuilder};
+
+#[test]
+fn builder_sets_output_and_step_budget() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::builder()
+        .output(output.clone())
+        .step_budget(50)
+        .build();
+
+    let statements = rlox::parse("print 1; while (true) {}".to_string()).unwrap();
+    let err = interpreter.interpret(statements).unwrap_err();
+
+    assert_eq!(
+        String::from_utf8(output.borrow().clone()).unwrap(),
+        "1.00\n"
+    );
+    assert_eq!(err.to_string(), "Execution step budget of 50 exceeded.");
+}
+
+#[test]
+fn scanner_builder_and_defaults_compose() {
+    let (tokens, _) = ScannerBuilder::new("print 1; // note".to_string())
+        .keep_comments(true)
+        .build()
+        .scan_tokens();
+
+    assert_eq!(tokens.len(), 5);
+    assert!(Parser::new(tokens).parse().is_ok());
+    assert!(Parser::default().parse().unwrap().is_empty());
+}
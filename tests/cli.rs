@@ -0,0 +1,181 @@
+use std::process::Command;
+
+fn rlox(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn check_accepts_clean_file_without_running_it() {
+    let output = rlox(&["--check", "tests/check/clean.lox"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn check_reports_every_error_in_broken_file() {
+    let output = rlox(&["--check", "tests/check/broken.lox"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(65));
+    assert!(output.stdout.is_empty());
+    assert_eq!(stderr.lines().count(), 2, "{stderr}");
+}
+
+#[test]
+fn fmt_keeps_comments() {
+    let output = rlox(&["--fmt", "tests/check/commented.lox"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout,
+        "// Adds one.\nfun inc(x) {\n  return x + 1; // done\n}\nprint inc(1);\n"
+    );
+}
+
+#[test]
+fn ast_json_prints_statement_tree() {
+    let output = rlox(&["--ast-json", "--eval", "print 1 + 2;"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    for kind in ["\"Print\"", "\"Binary\"", "\"Literal\""] {
+        assert!(stdout.contains(kind), "{kind} missing from {stdout}");
+    }
+}
+
+#[test]
+fn eval_accepts_trailing_expression_without_semicolon() {
+    let output = rlox(&["--eval", "var a = 1; a + 1"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2.00\n");
+}
+
+#[test]
+fn file_mode_still_requires_semicolon() {
+    assert!(rlox::parse("1 + 1".to_string()).is_err());
+    assert!(rlox::parse_eval("1 + 1".to_string()).is_ok());
+    assert!(rlox::parse_eval("1 + 1 print 2;".to_string()).is_err());
+}
+
+#[test]
+fn seed_flag_makes_random_reproducible() {
+    let program = "print random(); print randomInt(1, 6);";
+    let first = rlox(&["--seed", "1234", "--eval", program]);
+    let second = rlox(&["--seed", "1234", "--eval", program]);
+
+    assert_eq!(first.status.code(), Some(0));
+    assert!(!first.stdout.is_empty());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn exit_sets_the_process_exit_code() {
+    let output = rlox(&["--eval", "print 1; exit(7); print 2;"]);
+
+    assert_eq!(output.status.code(), Some(7));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1.00\n");
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn unused_local_warning_goes_to_stderr_without_failing() {
+    let output = rlox(&["--eval", "{ var unused = 1; print 2; }"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2.00\n");
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "[line 1] Warning: Local variable 'unused' is never read.\n"
+    );
+}
+
+#[test]
+fn color_never_keeps_errors_plain() {
+    let output = rlox(&["--color=never", "--eval", "print missing;"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(70));
+    assert!(!stderr.contains('\x1b'), "{stderr:?}");
+}
+
+#[test]
+fn color_always_paints_errors_red() {
+    let output = rlox(&["--color=always", "--eval", "print missing;"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(stderr.starts_with("\x1b[31m"), "{stderr:?}");
+}
+
+#[test]
+fn parse_error_is_an_err_and_exits_65() {
+    assert!(matches!(
+        rlox::parse("print (1;".to_string()),
+        Err(rlox::errors::RloxError::Parse(_))
+    ));
+
+    let output = rlox(&["--eval", "print (1;"]);
+
+    assert_eq!(output.status.code(), Some(65));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn runtime_error_exits_70() {
+    let output = rlox(&["--eval", "print 1; print -\"a\";"]);
+
+    assert_eq!(output.status.code(), Some(70));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1.00\n");
+}
+
+#[test]
+fn missing_file_exits_74() {
+    let output = rlox(&["tests/lox/does_not_exist.lox"]);
+
+    assert_eq!(output.status.code(), Some(74));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn scan_error_exits_65() {
+    let output = rlox(&["--eval", "print 1 @ 2;"]);
+
+    assert_eq!(output.status.code(), Some(65));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn eval_runs_program_from_argument() {
+    let output = rlox(&["--eval", "print 6 * 7;"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8(output.stdout).unwrap().contains("42"));
+}
+
+#[test]
+fn max_steps_stops_an_infinite_loop() {
+    let output = rlox(&["--max-steps", "1000", "--eval", "while (true) {}"]);
+
+    assert_eq!(output.status.code(), Some(70));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Execution step budget of 1000 exceeded.\n"
+    );
+}
+
+#[test]
+fn failed_assert_eq_exits_70() {
+    let output = rlox(&["--eval", "assert_eq(1 + 1, 3);"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(70));
+    assert!(
+        stderr.contains("2.00") && stderr.contains("3.00"),
+        "{stderr}"
+    );
+}
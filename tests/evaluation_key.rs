@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use rlox::{
+    interpreter::{Evaluation, EvaluationKey, Interpreter},
+    stmt::Stmt,
+};
+
+fn evaluate(source: &str) -> Evaluation {
+    let Stmt::Expression(stmt) = rlox::parse(format!("{source};")).unwrap().remove(0) else {
+        panic!("{source} is not an expression");
+    };
+
+    Interpreter::new().evaluate(stmt.expr).unwrap()
+}
+
+fn key(source: &str) -> EvaluationKey {
+    EvaluationKey::try_from(&evaluate(source)).unwrap()
+}
+
+#[test]
+fn mixed_type_keys_hash_by_value() {
+    let mut map = HashMap::new();
+    map.insert(key("\"one\""), 1);
+    map.insert(key("1"), 2);
+    map.insert(key("true"), 3);
+    map.insert(key("nil"), 4);
+
+    assert_eq!(map.len(), 4);
+    assert_eq!(map[&key("\"one\"")], 1);
+    assert_eq!(map[&key("1")], 2);
+    assert_eq!(map[&key("true")], 3);
+    assert_eq!(map[&key("nil")], 4);
+    assert!(!map.contains_key(&key("false")));
+}
+
+#[test]
+fn equal_numbers_are_the_same_key() {
+    assert_eq!(key("1"), key("1.0"));
+    assert_eq!(key("0"), key("-0"));
+    assert_ne!(key("1"), key("\"1\""));
+}
+
+#[test]
+fn functions_are_not_valid_keys() {
+    let err = EvaluationKey::try_from(&evaluate("clock")).unwrap_err();
+
+    assert_eq!(err.to_string(), "Can't use <native fn clock> as a map key.");
+}
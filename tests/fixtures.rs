@@ -0,0 +1,41 @@
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+
+fn run_fixture(path: &Path) -> String {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let result = rlox::run_file(path, output.clone());
+
+    let mut output = String::from_utf8(output.borrow().clone()).unwrap();
+    if let Err(err) = result {
+        output.push_str(&format!("{err}\n"));
+    }
+
+    output
+}
+
+#[test]
+fn lox_fixtures() {
+    let mut paths = fs::read_dir("tests/lox")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lox"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let failures = paths
+        .iter()
+        .filter_map(|path| {
+            let expected = fs::read_to_string(path.with_extension("expected")).unwrap();
+            let actual = run_fixture(path);
+
+            (actual != expected).then(|| {
+                format!(
+                    "{}\n--- expected\n{expected}--- actual\n{actual}",
+                    path.display()
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,69 @@
+use rlox::formatter;
+
+fn format(source: &str) -> String {
+    formatter::format(&rlox::parse(source.to_string()).unwrap())
+}
+
+const CASES: &[(&str, &str)] = &[
+    ("print 1+2*3;", "print 1 + 2 * 3;\n"),
+    (
+        "fun add(a,b){return a+b;}",
+        "fun add(a, b) {\n  return a + b;\n}\n",
+    ),
+    (
+        "var i=0;while(i<3){if(i==1){print \"one\";}else print i;i=i+1;}",
+        "var i = 0;\nwhile (i < 3) {\n  if (i == 1) {\n    print \"one\";\n  } else print i;\n  i = i + 1;\n}\n",
+    ),
+    (
+        "for(var i=0;i<2;i=i+1)print -(i);",
+        "for (var i = 0; i < 2; i = i + 1) print -(i);\n",
+    ),
+    (
+        "var v={var x=1.0;x+1};",
+        "var v = {\n  var x = 1.0;\n  x + 1\n};\n",
+    ),
+];
+
+#[test]
+fn formats_canonically() {
+    for (source, expected) in CASES {
+        assert_eq!(&format(source), expected, "formatting {source:?}");
+    }
+}
+
+#[test]
+fn formatting_is_idempotent() {
+    for (source, _) in CASES {
+        let once = format(source);
+        assert_eq!(format(&once), once, "re-formatting {source:?}");
+    }
+}
+
+const COMMENT_CASES: &[(&str, &str)] = &[
+    (
+        "// header\nvar a=1; // trailing\n/* footer */",
+        "// header\nvar a = 1; // trailing\n/* footer */\n",
+    ),
+    (
+        "fun f(x){ // opener\n// inside\nreturn x+/* mid */1;\n// before close\n}",
+        "fun f(x) {\n  // opener\n  // inside\n  /* mid */\n  return x + 1;\n  // before close\n}\n",
+    ),
+    (
+        "var v={var x=1; // one\nx // value\n};",
+        "var v = {\n  var x = 1; // one\n  x // value\n};\n",
+    ),
+    ("if(true){/* empty */}", "if (true) {\n  /* empty */\n}\n"),
+];
+
+#[test]
+fn formatting_keeps_comments() {
+    for (source, expected) in COMMENT_CASES {
+        let formatted = rlox::format_source(source.to_string()).unwrap();
+        assert_eq!(&formatted, expected, "formatting {source:?}");
+        assert_eq!(
+            rlox::format_source(formatted.clone()).unwrap(),
+            formatted,
+            "re-formatting {source:?}"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use rlox::lint::{lint, Warning};
+
+fn warnings(source: &str) -> Vec<Warning> {
+    lint(&rlox::parse(source.to_string()).unwrap())
+}
+
+#[test]
+fn unread_local_is_reported() {
+    assert_eq!(
+        warnings("{ var x = 1; }"),
+        vec![Warning::UnusedVariable {
+            name: "x".to_string(),
+            line: 1
+        }]
+    );
+}
+
+#[test]
+fn read_local_is_not_reported() {
+    assert!(warnings("{ var x = 1; print x; }").is_empty());
+}
+
+#[test]
+fn globals_are_exempt() {
+    assert!(warnings("var x = 1;").is_empty());
+}
+
+#[test]
+fn shadowing_read_does_not_count_for_the_outer_local() {
+    assert_eq!(
+        warnings("{ var x = 1;\n { var x = 2; print x; } }"),
+        vec![Warning::UnusedVariable {
+            name: "x".to_string(),
+            line: 1
+        }]
+    );
+}
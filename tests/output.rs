@@ -0,0 +1,140 @@
+use std::{
+    cell::RefCell,
+    io::{BufReader, Cursor, Read, Write},
+    rc::Rc,
+};
+
+use rlox::{
+    errors::{InterpretError, RloxError},
+    interpreter::Interpreter,
+};
+
+#[derive(Default)]
+struct RecordingWriter {
+    events: Vec<String>,
+}
+
+impl Write for RecordingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.events.push(String::from_utf8_lossy(buf).to_string());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.events.push("<flush>".to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn print_flushes_after_each_line() {
+    let output = Rc::new(RefCell::new(RecordingWriter::default()));
+
+    rlox::run("print \"a\"; print \"b\";".to_string(), output.clone()).unwrap();
+
+    let events = output.borrow().events.join("");
+    assert_eq!(events, "a\n<flush>b\n<flush>");
+}
+
+// Logs each read into the output's events, so a test can see where reads
+// fall between writes and flushes.
+struct RecordingReader {
+    output: Rc<RefCell<RecordingWriter>>,
+    input: Cursor<&'static [u8]>,
+}
+
+impl Read for RecordingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.output.borrow_mut().events.push("<read>".to_string());
+        self.input.read(buf)
+    }
+}
+
+#[test]
+fn prompt_is_flushed_before_input_is_read() {
+    let output = Rc::new(RefCell::new(RecordingWriter::default()));
+    let input = RecordingReader {
+        output: output.clone(),
+        input: Cursor::new(b"Ada\n"),
+    };
+    let mut interpreter = Interpreter::builder()
+        .output(output.clone())
+        .input(Rc::new(RefCell::new(BufReader::new(input))))
+        .build();
+
+    let source = "print \"Name?\"; var name = input(); print \"Hi \" + name;";
+    interpreter
+        .interpret(rlox::parse(source.to_string()).unwrap())
+        .unwrap();
+
+    let events = output.borrow().events.join("");
+    assert_eq!(events, "Name?\n<flush><read>Hi Ada\n<flush>");
+}
+
+#[test]
+fn exit_stops_the_program_with_its_code() {
+    let output = Rc::new(RefCell::new(RecordingWriter::default()));
+
+    let result = rlox::run(
+        "print \"a\"; exit(3); print \"b\";".to_string(),
+        output.clone(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RloxError::Runtime(InterpretError::Exit { code: 3 }))
+    ));
+    assert_eq!(output.borrow().events.join(""), "a\n<flush><flush>");
+}
+
+#[test]
+fn write_prints_without_a_newline() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    rlox::run("write \"a\"; write \"b\";".to_string(), output.clone()).unwrap();
+
+    assert_eq!(String::from_utf8(output.borrow().clone()).unwrap(), "ab");
+}
+
+#[test]
+fn exit_is_not_caught_by_try() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let result = rlox::run(
+        "try { exit(4); } catch (e) { print e; }".to_string(),
+        output.clone(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RloxError::Runtime(InterpretError::Exit { code: 4 }))
+    ));
+    assert!(output.borrow().is_empty());
+}
+
+#[test]
+fn step_budget_is_not_caught_by_try() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = rlox::interpreter::Interpreter::builder()
+        .output(output.clone())
+        .step_budget(1000)
+        .build();
+
+    let statements = rlox::parse("try { while (true) {} } catch (e) {}".to_string()).unwrap();
+    let err = interpreter.interpret(statements).unwrap_err();
+
+    assert_eq!(err.to_string(), "Execution step budget of 1000 exceeded.");
+}
+
+#[test]
+fn stack_overflow_is_not_caught_by_try() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let result = rlox::run(
+        "fun f() { f(); } try { f(); } catch (e) { print e; }".to_string(),
+        output.clone(),
+    );
+
+    assert!(result.unwrap_err().to_string().contains("Stack overflow."));
+    assert!(output.borrow().is_empty());
+}
@@ -0,0 +1,318 @@
+use rlox::{errors::ParseError, parser::Parser, scanner::Scanner};
+
+fn statement_spans(source: &str) -> Vec<&str> {
+    rlox::parse(source.to_string())
+        .unwrap()
+        .iter()
+        .map(|stmt| {
+            let (start, end) = stmt.span();
+            &source[start..end]
+        })
+        .collect()
+}
+
+#[test]
+fn statement_span_covers_whole_statement() {
+    assert_eq!(statement_spans("print 1;"), vec!["print 1;"]);
+}
+
+#[test]
+fn statement_spans_are_byte_ranges() {
+    let source = "var é = \"ü\";\n  { print é; }\nfun f() {}";
+
+    assert_eq!(
+        statement_spans(source),
+        vec!["var é = \"ü\";", "{ print é; }", "fun f() {}"]
+    );
+}
+
+#[test]
+fn nested_statements_and_expressions_carry_spans() {
+    use rlox::{expr::Expr, stmt::Stmt};
+
+    let source = "{ x = -a + f(b, (c)); }";
+    let statements = rlox::parse(source.to_string()).unwrap();
+    let text = |span: (usize, usize)| &source[span.0..span.1];
+
+    let Stmt::Block(block) = &statements[0] else {
+        panic!("expected a block");
+    };
+    let Stmt::Expression(stmt) = &block.statements[0] else {
+        panic!("expected an expression statement");
+    };
+    assert_eq!(text(stmt.span), "x = -a + f(b, (c));");
+
+    let Expr::Assign(assign) = &stmt.expr else {
+        panic!("expected an assignment");
+    };
+    assert_eq!(text(assign.span), "x = -a + f(b, (c))");
+
+    let Expr::Binary(binary) = &*assign.value else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(text(binary.left.span()), "-a");
+    assert_eq!(text(binary.right.span()), "f(b, (c))");
+
+    let Expr::Call(call) = &*binary.right else {
+        panic!("expected a call");
+    };
+    assert_eq!(text(call.callee.span()), "f");
+    assert_eq!(text(call.arguments[1].span()), "(c)");
+}
+
+#[test]
+fn each_declaration_in_a_var_list_has_its_own_span() {
+    let source = "var a = 1, b;";
+    let statements = rlox::parse(source.to_string()).unwrap();
+
+    let rlox::stmt::Stmt::VarMulti(stmt) = &statements[0] else {
+        panic!("expected a multi-variable declaration");
+    };
+    assert_eq!(
+        stmt.declarations
+            .iter()
+            .map(|var| &source[var.span.0..var.span.1])
+            .collect::<Vec<_>>(),
+        vec!["a = 1", "b"]
+    );
+    assert_eq!(statements[0].span(), (0, source.len()));
+}
+
+fn parse_source(source: &str) -> Vec<rlox::stmt::Stmt> {
+    let (tokens, errors) = Scanner::new(source.to_string()).scan_tokens();
+    assert!(errors.is_empty());
+    assert_eq!(tokens.len(), 1, "expected only the EOF token");
+
+    Parser::new(tokens).parse().unwrap()
+}
+
+#[test]
+fn empty_program_parses_to_no_statements() {
+    assert!(parse_source("").is_empty());
+}
+
+#[test]
+fn whitespace_only_program_parses_to_no_statements() {
+    assert!(parse_source("  \n\t\r\n ").is_empty());
+}
+
+#[test]
+fn comment_only_program_parses_to_no_statements() {
+    assert!(parse_source("// nothing here\n// or here").is_empty());
+}
+
+#[test]
+fn token_stream_without_eof_is_a_parse_error() {
+    let (mut tokens, _) = Scanner::new("print 1 +".to_string()).scan_tokens();
+    tokens.pop();
+
+    let errors = Parser::new(tokens).parse().unwrap_err();
+    assert!(
+        matches!(errors[0], ParseError::ParseEOF { .. }),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn empty_token_stream_parses_to_no_statements() {
+    assert!(Parser::new(Vec::new()).parse().unwrap().is_empty());
+}
+
+#[test]
+fn number_token_without_literal_is_a_parse_error() {
+    let (mut tokens, _) = Scanner::new("print 1;".to_string()).scan_tokens();
+    tokens[1].literal = None;
+
+    let errors = Parser::new(tokens).parse().unwrap_err();
+    assert_eq!(errors[0].to_string(), "1 at '1' Expect literal value.");
+}
+
+#[test]
+fn eval_expr_evaluates_a_bare_expression() {
+    assert_eq!(
+        rlox::eval_expr("2 + 3 * 4").unwrap(),
+        rlox::interpreter::Evaluation::f64(14.0)
+    );
+}
+
+#[test]
+fn eval_expr_rejects_trailing_tokens() {
+    assert_eq!(
+        rlox::eval_expr("1 2").unwrap_err().to_string(),
+        "1 at '2' Expect end of expression."
+    );
+}
+
+#[test]
+fn parser_ignores_comment_tokens() {
+    let mut scanner = Scanner::new("print 1; // done".to_string());
+    scanner.keep_comments = true;
+    let (tokens, _) = scanner.scan_tokens();
+
+    assert_eq!(Parser::new(tokens).parse().unwrap().len(), 1);
+}
+
+fn print_expression(source: &str) -> String {
+    let (tokens, _) = Scanner::new(source.to_string()).scan_tokens();
+    rlox::ast::ast_print(Parser::new(tokens).parse_expression().unwrap())
+}
+
+#[test]
+fn primary_forms_parse() {
+    let cases = [
+        ("true", "(true)"),
+        ("false", "(false)"),
+        ("nil", "(nil)"),
+        ("12", "(12)"),
+        ("\"s\"", "(s)"),
+        ("name", "(name)"),
+        ("(1)", "(group (1))"),
+        ("{ 1 }", "(block-expr (1))"),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(print_expression(source), expected, "{source}");
+    }
+}
+
+#[test]
+fn unexpected_token_is_reported_where_it_appears() {
+    let (tokens, _) = Scanner::new("print );".to_string()).scan_tokens();
+    let errors = Parser::new(tokens).parse().unwrap_err();
+
+    assert_eq!(errors[0].to_string(), "1 at ')' Expect expression.");
+}
+
+#[test]
+fn var_keyword_is_not_an_expression() {
+    let (tokens, _) = Scanner::new("print var;".to_string()).scan_tokens();
+    let errors = Parser::new(tokens).parse().unwrap_err();
+
+    assert_eq!(errors[0].to_string(), "1 at 'var' Expect expression.");
+}
+
+#[test]
+fn grouping_without_closing_paren_is_an_error() {
+    let (tokens, _) = Scanner::new("print (1 + 2;".to_string()).scan_tokens();
+    let errors = Parser::new(tokens).parse().unwrap_err();
+
+    assert_eq!(
+        errors[0].to_string(),
+        "1 at ';' Expect ')' after expression."
+    );
+}
+
+#[test]
+fn grouping_cut_off_at_end_of_input_is_an_error() {
+    let (tokens, _) = Scanner::new("(1 + 2".to_string()).scan_tokens();
+
+    assert!(matches!(
+        Parser::new(tokens).parse_expression(),
+        Err(ParseError::ParseEOF { .. })
+    ));
+}
+
+#[test]
+fn empty_blocks_parse_and_run() {
+    for source in ["{}", "if (true) {}", "while (false) {}", "fun f() {} f();"] {
+        let output = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        rlox::run(source.to_string(), output.clone()).unwrap();
+        assert!(output.borrow().is_empty(), "{source}");
+    }
+}
+
+#[test]
+fn empty_block_has_no_statements() {
+    let statements = rlox::parse("{}".to_string()).unwrap();
+
+    assert!(
+        matches!(&statements[..], [rlox::stmt::Stmt::Block(block)] if block.statements.is_empty())
+    );
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    assert_eq!(print_expression("a or b and c"), "(or (a) (and (b) (c)))");
+}
+
+#[test]
+fn equality_binds_tighter_than_and() {
+    assert_eq!(print_expression("a == b and c"), "(and (== (a) (b)) (c))");
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    assert_eq!(
+        rlox::eval_expr("nil or \"yes\"").unwrap().to_string(),
+        "yes"
+    );
+    assert_eq!(
+        rlox::eval_expr("false and undefined").unwrap().to_string(),
+        "false"
+    );
+}
+
+#[test]
+fn stmt_print_renders_var_if_and_function() {
+    let statements = rlox::parse(
+        "var x = 1; if (x) print x; else print 2; fun add(a, b) { return a + b; }".to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        statements
+            .iter()
+            .map(rlox::ast::stmt_print)
+            .collect::<Vec<_>>(),
+        vec![
+            "(var x = (1))",
+            "(if (x) (print (x)) (print (2)))",
+            "(fun add (a b) (return (+ (a) (b))))",
+        ]
+    );
+}
+
+#[test]
+fn equality_operators_parse_as_binary() {
+    assert_eq!(print_expression("a == b"), "(== (a) (b))");
+    assert_eq!(print_expression("a != b == c"), "(== (!= (a) (b)) (c))");
+}
+
+fn print_statements(source: &str) -> Vec<String> {
+    rlox::parse(source.to_string())
+        .unwrap()
+        .iter()
+        .map(rlox::ast::stmt_print)
+        .collect()
+}
+
+#[test]
+fn dangling_else_after_unbraced_for_belongs_to_the_if() {
+    assert_eq!(
+        print_statements("if (true) for (;false;) print 1; else print 2;"),
+        vec!["(if (true) (for _ (false) _ (print (1))) (print (2)))"]
+    );
+}
+
+#[test]
+fn else_after_braced_for_belongs_to_the_loop() {
+    assert_eq!(
+        print_statements("if (true) { for (;false;) { print 1; } else print 2; }"),
+        vec!["(if (true) (block (for _ (false) _ (block (print (1))) (print (2)))))"]
+    );
+}
+
+#[test]
+fn else_after_braced_loop_in_unbraced_if_is_ambiguous() {
+    for source in [
+        "if (true) for (;false;) { print 1; } else print 2;",
+        "if (true) while (false) { print 1; } else print 2;",
+    ] {
+        let errors = rlox::parse(source.to_string()).unwrap_err();
+        assert_eq!(
+            errors.to_string(),
+            "1 at 'else' Ambiguous 'else' after a loop in an if; add braces around the if's branch.",
+            "parsing {source:?}"
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::{
+    interpreter::Interpreter,
+    profile::{NodeKind, Profile},
+};
+
+#[test]
+fn profile_counts_loop_nodes() {
+    let statements =
+        rlox::parse("var i = 0; while (i < 10) { i = i + 1; } print i;".to_string()).unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.output = Rc::new(RefCell::new(Vec::new()));
+    interpreter.profile = Some(Profile::default());
+    interpreter.interpret(statements).unwrap();
+
+    let profile = interpreter.profile.unwrap();
+    assert_eq!(profile.count(NodeKind::While), 1);
+    assert_eq!(profile.count(NodeKind::Block), 10);
+    // Eleven `i < 10` checks plus ten `i + 1` additions.
+    assert_eq!(profile.count(NodeKind::Binary), 21);
+    assert_eq!(profile.count(NodeKind::Call), 0);
+}
@@ -0,0 +1,65 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::interpreter::Interpreter;
+
+fn run_seeded(seed: u64, source: &str) -> String {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output.clone();
+    interpreter.rng_state = seed;
+
+    interpreter
+        .interpret(rlox::parse(source.to_string()).unwrap())
+        .unwrap();
+
+    let output = output.borrow().clone();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn same_seed_gives_same_sequence() {
+    let source = "print random(); print random(); print randomInt(1, 100);";
+
+    let first = run_seeded(42, source);
+    assert_eq!(first, run_seeded(42, source));
+    assert_ne!(first, run_seeded(43, source));
+}
+
+#[test]
+fn random_stays_in_unit_interval() {
+    let source =
+        "for (var i = 0; i < 1000; i = i + 1) { var r = random(); if (r < 0) print r; if (r >= 1) print r; }";
+
+    assert_eq!(run_seeded(7, source), "");
+}
+
+#[test]
+fn random_int_respects_inclusive_bounds() {
+    let source = "
+        var low = false;
+        var high = false;
+        for (var i = 0; i < 1000; i = i + 1) {
+            var n = randomInt(-2, 2);
+            if (n < -2) print n;
+            if (n > 2) print n;
+            if (n == -2) low = true;
+            if (n == 2) high = true;
+        }
+        print low;
+        print high;
+        print randomInt(5, 5);
+    ";
+
+    assert_eq!(run_seeded(7, source), "true\ntrue\n5.00\n");
+}
+
+#[test]
+fn random_int_rejects_reversed_bounds() {
+    let mut interpreter = Interpreter::new();
+    let statements = rlox::parse("randomInt(3, 1);".to_string()).unwrap();
+
+    assert_eq!(
+        interpreter.interpret(statements).unwrap_err().to_string(),
+        "[line 1] randomInt expects two integers with lo <= hi, got 3.00 and 1.00."
+    );
+}
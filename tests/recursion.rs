@@ -0,0 +1,62 @@
+use std::{cell::RefCell, rc::Rc, thread};
+
+use rlox::interpreter::Interpreter;
+
+#[test]
+fn infinite_recursion_is_a_clean_error() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let err = rlox::run("fun f(n) { return f(n + 1); } f(0);".to_string(), output).unwrap_err();
+
+    assert!(err.to_string().contains("Stack overflow."), "{err}");
+}
+
+#[test]
+fn recursion_within_the_limit_succeeds() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    rlox::run(
+        "fun count(n) { if (n == 0) return 0; return 1 + count(n - 1); } print count(900);"
+            .to_string(),
+        output.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.borrow().clone()).unwrap(),
+        "900.00\n"
+    );
+}
+
+#[test]
+fn max_call_depth_is_configurable() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::builder()
+        .output(output)
+        .max_call_depth(10)
+        .build();
+
+    let statements = rlox::parse("fun f(n) { if (n < 20) f(n + 1); } f(0);".to_string()).unwrap();
+
+    assert_eq!(
+        interpreter.interpret(statements).unwrap_err().to_string(),
+        "[line 1] Stack overflow."
+    );
+}
+
+#[test]
+fn default_limit_holds_on_a_small_host_stack() {
+    let result = thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(|| {
+            let output = Rc::new(RefCell::new(Vec::new()));
+            rlox::run("fun f(n) { return f(n + 1); } f(0);".to_string(), output)
+                .unwrap_err()
+                .to_string()
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert_eq!(result, "[line 1] Stack overflow.");
+}
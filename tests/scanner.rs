@@ -0,0 +1,191 @@
+use rlox::{
+    scanner::Scanner,
+    token::{Literal, TokenType},
+};
+
+#[test]
+fn backslash_newline_continues_the_line() {
+    let mut scanner = Scanner::new("1 + \\\n 2;\nprint".to_string());
+    let (tokens, errors) = scanner.scan_tokens();
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| (token.token_type, token.line))
+            .collect::<Vec<_>>(),
+        vec![
+            (TokenType::Number, 1),
+            (TokenType::Plus, 1),
+            (TokenType::Number, 2),
+            (TokenType::Semicolon, 2),
+            (TokenType::Print, 3),
+            (TokenType::EOF, 3),
+        ]
+    );
+}
+
+#[test]
+fn stray_backslash_is_an_error() {
+    let mut scanner = Scanner::new("1 \\ 2".to_string());
+    scanner.scan_tokens();
+
+    assert!(scanner.had_errors());
+}
+
+#[test]
+fn integer_and_float_literals_are_distinguished() {
+    let mut scanner = Scanner::new("3 3.0 99999999999999999999".to_string());
+    let (tokens, _) = scanner.scan_tokens();
+
+    assert!(matches!(tokens[0].literal, Some(Literal::int(3))));
+    assert!(matches!(tokens[1].literal, Some(Literal::f64(n)) if n == 3.0));
+    assert!(matches!(tokens[2].literal, Some(Literal::f64(_))));
+}
+
+fn token_types(source: &str) -> Vec<TokenType> {
+    let mut scanner = Scanner::new(source.to_string());
+    let (tokens, errors) = scanner.scan_tokens();
+    assert!(errors.is_empty());
+
+    tokens.iter().map(|token| token.token_type).collect()
+}
+
+#[test]
+fn literal_keywords_scan_as_keywords() {
+    assert_eq!(
+        token_types("true false nil"),
+        vec![
+            TokenType::True,
+            TokenType::False,
+            TokenType::Nil,
+            TokenType::EOF
+        ]
+    );
+}
+
+#[test]
+fn keyword_prefixes_scan_as_identifiers() {
+    assert_eq!(
+        token_types("truean falsey nile orchid one or"),
+        vec![
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::Or,
+            TokenType::EOF
+        ]
+    );
+}
+
+#[test]
+fn trailing_comment_without_newline_scans_to_eof() {
+    assert_eq!(
+        token_types("print 1; // trailing"),
+        vec![
+            TokenType::Print,
+            TokenType::Number,
+            TokenType::Semicolon,
+            TokenType::EOF
+        ]
+    );
+    assert_eq!(token_types("//"), vec![TokenType::EOF]);
+}
+
+#[test]
+fn comments_are_kept_as_tokens_when_enabled() {
+    let mut scanner =
+        Scanner::new("// head\nprint 1; /* inline\nblock */ print 2; // tail".to_string());
+    scanner.keep_comments = true;
+    let (tokens, errors) = scanner.scan_tokens();
+
+    assert!(errors.is_empty());
+    let comments = tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::Comment)
+        .map(|token| (token.lexeme.as_str(), token.line))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        comments,
+        vec![("// head", 1), ("/* inline\nblock */", 2), ("// tail", 3)]
+    );
+}
+
+#[test]
+fn block_comments_are_skipped_by_default() {
+    assert_eq!(
+        token_types("1 /* two\nlines */ 2"),
+        vec![TokenType::Number, TokenType::Number, TokenType::EOF]
+    );
+}
+
+#[test]
+fn unterminated_block_comment_is_an_error() {
+    let mut scanner = Scanner::new("1 /* never closed".to_string());
+    scanner.scan_tokens();
+
+    assert!(scanner.had_errors());
+}
+
+#[test]
+fn unexpected_character_is_skipped_and_scanning_continues() {
+    let mut scanner = Scanner::new("1 @ 2".to_string());
+    let (tokens, errors) = scanner.scan_tokens();
+
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| token.token_type)
+            .collect::<Vec<_>>(),
+        vec![TokenType::Number, TokenType::Number, TokenType::EOF]
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "1 Unexpected character '@'.");
+}
+
+#[test]
+fn fun_is_a_keyword_but_function_is_not() {
+    assert_eq!(
+        token_types("fun function funny"),
+        vec![
+            TokenType::Fun,
+            TokenType::Identifier,
+            TokenType::Identifier,
+            TokenType::EOF
+        ]
+    );
+}
+
+#[test]
+fn crlf_source_counts_lines_and_normalizes_strings() {
+    let mut scanner = Scanner::new("var a = 1;\r\nvar b = \"x\r\ny\";\r\nprint b;".to_string());
+    let (tokens, errors) = scanner.scan_tokens();
+
+    assert!(errors.is_empty());
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|token| (token.lexeme.as_str(), token.line))
+            .filter(|(lexeme, _)| ["var", "print"].contains(lexeme))
+            .collect::<Vec<_>>(),
+        vec![("var", 1), ("var", 2), ("print", 4)]
+    );
+
+    let string = tokens
+        .iter()
+        .find(|token| token.token_type == TokenType::String)
+        .unwrap();
+    assert!(matches!(&string.literal, Some(Literal::string(s)) if s == "x\ny"));
+    assert_eq!(string.line, 3);
+}
+
+#[test]
+fn two_bad_characters_give_two_errors() {
+    let mut scanner = Scanner::new("var a = 1 @ 2;\nprint # a;".to_string());
+    let (_, errors) = scanner.scan_tokens();
+
+    assert!(scanner.had_errors());
+    assert_eq!(errors.len(), 2);
+}
@@ -0,0 +1,43 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::interpreter::Interpreter;
+
+#[test]
+fn runtime_error_in_block_restores_outer_environment() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output.clone();
+
+    let failing =
+        rlox::parse("var outer = 1; { var inner = 2; { missing; } }".to_string()).unwrap();
+    assert!(interpreter.interpret(failing).is_err());
+
+    let after = rlox::parse("print outer; print env_depth(); inner;".to_string()).unwrap();
+    let result = interpreter.interpret(after);
+
+    assert_eq!(
+        String::from_utf8(output.borrow().clone()).unwrap(),
+        "1.00\n0.00\n"
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "[line 1] Undefined variable 'inner'."
+    );
+}
+
+#[test]
+fn env_depth_counts_enclosing_blocks() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    rlox::run(
+        "print env_depth(); { print env_depth(); { print env_depth(); } print env_depth(); } print env_depth();"
+            .to_string(),
+        output.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.borrow().clone()).unwrap(),
+        "0.00\n1.00\n2.00\n1.00\n0.00\n"
+    );
+}
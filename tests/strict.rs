@@ -0,0 +1,47 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rlox::interpreter::Interpreter;
+
+fn run(strict: bool, source: &str) -> Result<String, String> {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut interpreter = Interpreter::new();
+    interpreter.output = output.clone();
+    interpreter.strict = strict;
+
+    let result = interpreter.interpret(rlox::parse(source.to_string()).unwrap());
+    let printed = String::from_utf8(output.borrow().clone()).unwrap();
+
+    result.map(|_| printed).map_err(|err| err.to_string())
+}
+
+#[test]
+fn strict_allows_type_stable_reassignment() {
+    assert_eq!(
+        run(true, "var x = 1; x = 2; print x;"),
+        Ok("2.00\n".to_string())
+    );
+}
+
+#[test]
+fn strict_rejects_type_changing_reassignment() {
+    assert_eq!(
+        run(true, "var x = 1;\nx = \"s\";"),
+        Err("[line 2] Can't assign a string to 'x', which holds a number.".to_string())
+    );
+}
+
+#[test]
+fn strict_lets_an_uninitialized_variable_take_its_first_type() {
+    assert_eq!(
+        run(true, "var x; x = \"s\"; print x;"),
+        Ok("s\n".to_string())
+    );
+}
+
+#[test]
+fn non_strict_allows_type_changing_reassignment() {
+    assert_eq!(
+        run(false, "var x = 1; x = \"s\"; print x;"),
+        Ok("s\n".to_string())
+    );
+}
@@ -0,0 +1,39 @@
+use std::time::{Duration, Instant};
+
+use rlox::interpreter::Interpreter;
+
+#[test]
+fn infinite_loop_times_out() {
+    let mut interpreter = Interpreter::new();
+    let statements = rlox::parse("while (true) {}".to_string()).unwrap();
+
+    let started = Instant::now();
+    let err = interpreter
+        .run_with_timeout(statements, Duration::from_millis(50))
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "Execution timed out.");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn program_within_timeout_runs_to_completion() {
+    let mut interpreter = Interpreter::new();
+    let statements = rlox::parse("var x = 1 + 2;".to_string()).unwrap();
+
+    assert!(interpreter
+        .run_with_timeout(statements, Duration::from_secs(5))
+        .is_ok());
+}
+
+#[test]
+fn timeout_is_not_caught_by_try() {
+    let mut interpreter = Interpreter::new();
+    let statements = rlox::parse("try { while (true) {} } catch (e) {}".to_string()).unwrap();
+
+    let err = interpreter
+        .run_with_timeout(statements, Duration::from_millis(50))
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "Execution timed out.");
+}